@@ -0,0 +1,58 @@
+use crate::pixels::F32x2;
+use crate::{ImageView, ImageViewMut};
+
+pub(crate) fn multiply_alpha(src_image: &ImageView<F32x2>, dst_image: &mut ImageViewMut<F32x2>) {
+    let src_rows = src_image.iter_rows(0);
+    let dst_rows = dst_image.iter_rows_mut();
+
+    for (src_row, dst_row) in src_rows.zip(dst_rows) {
+        for (src_pixel, dst_pixel) in src_row.iter().zip(dst_row.iter_mut()) {
+            dst_pixel.0 = multiply_alpha_pixel(src_pixel.0);
+        }
+    }
+}
+
+pub(crate) fn multiply_alpha_inplace(image: &mut ImageViewMut<F32x2>) {
+    for row in image.iter_rows_mut() {
+        for pixel in row.iter_mut() {
+            pixel.0 = multiply_alpha_pixel(pixel.0);
+        }
+    }
+}
+
+#[inline(always)]
+fn multiply_alpha_pixel(mut components: [f32; 2]) -> [f32; 2] {
+    let alpha = components[1];
+    components[..1].iter_mut().for_each(|c| *c *= alpha);
+    components
+}
+
+// Divide
+
+pub(crate) fn divide_alpha(src_image: &ImageView<F32x2>, dst_image: &mut ImageViewMut<F32x2>) {
+    let src_rows = src_image.iter_rows(0);
+    let dst_rows = dst_image.iter_rows_mut();
+
+    for (src_row, dst_row) in src_rows.zip(dst_rows) {
+        for (src_pixel, dst_pixel) in src_row.iter().zip(dst_row.iter_mut()) {
+            dst_pixel.0 = divide_alpha_pixel(src_pixel.0);
+        }
+    }
+}
+
+pub(crate) fn divide_alpha_inplace(image: &mut ImageViewMut<F32x2>) {
+    for row in image.iter_rows_mut() {
+        for pixel in row.iter_mut() {
+            pixel.0 = divide_alpha_pixel(pixel.0);
+        }
+    }
+}
+
+#[inline(always)]
+fn divide_alpha_pixel(mut components: [f32; 2]) -> [f32; 2] {
+    let alpha = components[1];
+    // Fully transparent pixels have no color
+    let recip_alpha = if alpha == 0. { 0. } else { 1. / alpha };
+    components[..1].iter_mut().for_each(|c| *c *= recip_alpha);
+    components
+}
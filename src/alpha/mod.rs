@@ -3,6 +3,8 @@ use crate::CpuExtensions;
 use crate::{ImageView, ImageViewMut};
 
 mod common;
+mod f32x2;
+mod f32x4;
 mod u16x2;
 mod u16x4;
 mod u8x2;
@@ -14,6 +16,9 @@ where
 {
     /// Multiplies RGB-channels of source image by alpha-channel and store
     /// result into destination image.
+    ///
+    /// # Panics
+    /// If source and destination images have different sizes.
     fn multiply_alpha(
         src_image: &ImageView<Self>,
         dst_image: &mut ImageViewMut<Self>,
@@ -25,6 +30,9 @@ where
 
     /// Divides RGB-channels of source image by alpha-channel and store
     /// result into destination image.
+    ///
+    /// # Panics
+    /// If source and destination images have different sizes.
     fn divide_alpha(
         src_image: &ImageView<Self>,
         dst_image: &mut ImageViewMut<Self>,
@@ -34,3 +42,85 @@ where
     /// Divides RGB-channels of image by alpha-channel inplace.
     fn divide_alpha_inplace(image: &mut ImageViewMut<Self>, cpu_extensions: CpuExtensions);
 }
+
+/// Checks that source and destination images have the same size.
+///
+/// The trait methods are safe, so different sizes cause
+/// a panic regardless of the `checked` feature.
+#[inline(always)]
+pub(crate) fn check_sizes<P: PixelExt>(src_image: &ImageView<P>, dst_image: &ImageViewMut<P>) {
+    if src_image.width() != dst_image.width() || src_image.height() != dst_image.height() {
+        panic!(
+            "Size of source image ({}x{}) is not equal to size of destination image ({}x{})",
+            src_image.width(),
+            src_image.height(),
+            dst_image.width(),
+            dst_image.height()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use super::*;
+    use crate::pixels::{F32x2, F32x4, U8x4};
+    use crate::Image;
+
+    // Safe methods panic even without the `checked` feature
+    #[test]
+    #[should_panic(expected = "is not equal to size of destination image")]
+    fn test_different_sizes_panic() {
+        let src_image =
+            Image::<U8x4>::new(NonZeroU32::new(4).unwrap(), NonZeroU32::new(3).unwrap());
+        let mut dst_image =
+            Image::<U8x4>::new(NonZeroU32::new(4).unwrap(), NonZeroU32::new(2).unwrap());
+        U8x4::multiply_alpha(
+            &src_image.try_view().unwrap(),
+            &mut dst_image.try_view_mut().unwrap(),
+            CpuExtensions::None,
+        );
+    }
+
+    fn multiply_and_divide<P: AlphaMulDiv>(components: &[f32]) -> (Vec<f32>, Vec<f32>) {
+        let width = NonZeroU32::new((components.len() * 4 / P::size()) as u32).unwrap();
+        let height = NonZeroU32::new(1).unwrap();
+        let buffer = components.iter().flat_map(|c| c.to_ne_bytes()).collect();
+        let src_image = Image::<P>::try_from_vec_u8(width, height, buffer).unwrap();
+        let mut multiplied = Image::<P>::new(width, height);
+        let mut divided = Image::<P>::new(width, height);
+        P::multiply_alpha(
+            &src_image.try_view().unwrap(),
+            &mut multiplied.try_view_mut().unwrap(),
+            CpuExtensions::None,
+        );
+        P::divide_alpha(
+            &multiplied.try_view().unwrap(),
+            &mut divided.try_view_mut().unwrap(),
+            CpuExtensions::None,
+        );
+        let to_f32 = |image: Image<P>| {
+            image
+                .buffer()
+                .chunks_exact(4)
+                .map(|c| f32::from_ne_bytes(c.try_into().unwrap()))
+                .collect()
+        };
+        (to_f32(multiplied), to_f32(divided))
+    }
+
+    #[test]
+    fn test_f32_alpha() {
+        // Fractional results are kept and fully transparent
+        // pixels lose their color.
+        let (multiplied, divided) = multiply_and_divide::<F32x2>(&[0.5, 0.25, 0.3, 0.]);
+        assert_eq!(multiplied, [0.125, 0.25, 0., 0.]);
+        assert_eq!(divided, [0.5, 0.25, 0., 0.]);
+
+        let src = [0.5, 0.75, 0.125, 0.5, 0.3, 0.6, 0.9, 0.];
+        let (multiplied, divided) = multiply_and_divide::<F32x4>(&src);
+        assert_eq!(multiplied, [0.25, 0.375, 0.0625, 0.5, 0., 0., 0., 0.]);
+        assert_eq!(divided, [0.5, 0.75, 0.125, 0.5, 0., 0., 0., 0.]);
+    }
+}
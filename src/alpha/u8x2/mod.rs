@@ -2,7 +2,7 @@ use crate::pixels::U8x2;
 use crate::CpuExtensions;
 use crate::{ImageView, ImageViewMut};
 
-use super::AlphaMulDiv;
+use super::{check_sizes, AlphaMulDiv};
 
 #[cfg(target_arch = "x86_64")]
 mod avx2;
@@ -20,6 +20,7 @@ impl AlphaMulDiv for U8x2 {
         dst_image: &mut ImageViewMut<Self>,
         cpu_extensions: CpuExtensions,
     ) {
+        check_sizes(src_image, dst_image);
         match cpu_extensions {
             #[cfg(target_arch = "x86_64")]
             CpuExtensions::Avx2 => unsafe { avx2::multiply_alpha(src_image, dst_image) },
@@ -52,6 +53,7 @@ impl AlphaMulDiv for U8x2 {
         dst_image: &mut ImageViewMut<Self>,
         cpu_extensions: CpuExtensions,
     ) {
+        check_sizes(src_image, dst_image);
         match cpu_extensions {
             #[cfg(target_arch = "x86_64")]
             CpuExtensions::Avx2 => unsafe { avx2::divide_alpha(src_image, dst_image) },
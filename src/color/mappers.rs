@@ -0,0 +1,28 @@
+//! Predefined mappers of pixel components.
+use super::PixelComponentMapper;
+
+fn srgb_to_linear(v: f32) -> f32 {
+    if v < 0.04045 {
+        v / 12.92
+    } else {
+        ((v + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(v: f32) -> f32 {
+    if v < 0.0031308 {
+        v * 12.92
+    } else {
+        1.055 * v.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Creates mapper between sRGB and linear light.
+pub fn create_srgb_mapper() -> PixelComponentMapper {
+    PixelComponentMapper::new(srgb_to_linear, linear_to_srgb)
+}
+
+/// Creates mapper between values encoded with gamma 2.2 and linear light.
+pub fn create_gamma_22_mapper() -> PixelComponentMapper {
+    PixelComponentMapper::new(|v| v.powf(2.2), |v| v.powf(1.0 / 2.2))
+}
@@ -0,0 +1,360 @@
+//! Mapping of pixel components between gamma-encoded and linear values.
+use crate::pixels::{GetCount, IntoPixelComponent, PixelComponent, PixelExt};
+use crate::{Error, ImageView, ImageViewMut};
+
+pub mod mappers;
+
+/// Mapper of pixel components between gamma-encoded values
+/// and values of linear light.
+///
+/// Convolution of gamma-encoded values darkens fine details and edges
+/// of downscaled images. To avoid this, the source image should be
+/// mapped into 16-bit linear values with [`PixelComponentMapper::forward_map`],
+/// resized and mapped back into gamma-encoded values with
+/// [`PixelComponentMapper::backward_map`].
+///
+/// Alpha channel of pixels with two or four components is not
+/// gamma-encoded, so it is only converted between types of components.
+///
+/// # Examples
+///
+/// ```
+/// use std::num::NonZeroU32;
+/// use fer::{create_srgb_mapper, Image, Resizer, U16x4, U8x4};
+///
+/// let src_image = Image::<U8x4>::new(NonZeroU32::new(64).unwrap(), NonZeroU32::new(64).unwrap());
+/// let mut dst_image = Image::<U8x4>::new(NonZeroU32::new(16).unwrap(), NonZeroU32::new(16).unwrap());
+/// let mut linear_src = Image::<U16x4>::new(src_image.width(), src_image.height());
+/// let mut linear_dst = Image::<U16x4>::new(dst_image.width(), dst_image.height());
+///
+/// let mapper = create_srgb_mapper();
+/// mapper
+///     .try_forward_map(&src_image.try_view().unwrap(), &mut linear_src.try_view_mut().unwrap())
+///     .unwrap();
+/// let mut resizer = Resizer::default();
+/// unsafe {
+///     resizer.resize(&linear_src.view(), &mut linear_dst.view_mut());
+/// }
+/// mapper
+///     .try_backward_map(&linear_dst.try_view().unwrap(), &mut dst_image.try_view_mut().unwrap())
+///     .unwrap();
+/// ```
+#[derive(Clone)]
+pub struct PixelComponentMapper {
+    /// Gamma-encoded 8-bit values mapped into linear 16-bit values.
+    forward_u8: Vec<u16>,
+    /// Gamma-encoded 16-bit values mapped into linear 16-bit values.
+    forward_u16: Vec<u16>,
+    /// Linear 16-bit values mapped into gamma-encoded 8-bit values.
+    backward_u8: Vec<u8>,
+    /// Linear 16-bit values mapped into gamma-encoded 16-bit values.
+    backward_u16: Vec<u16>,
+}
+
+impl std::fmt::Debug for PixelComponentMapper {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PixelComponentMapper")
+            .finish_non_exhaustive()
+    }
+}
+
+impl PixelComponentMapper {
+    /// Creates mapper from functions which convert values in range
+    /// `[0.0, 1.0]` from gamma-encoded into linear and back.
+    pub fn new<F, B>(to_linear: F, from_linear: B) -> Self
+    where
+        F: Fn(f32) -> f32,
+        B: Fn(f32) -> f32,
+    {
+        let to_linear = |v: f32| (to_linear(v).clamp(0., 1.) * u16::MAX as f32).round() as u16;
+        let from_linear = |v: f32| from_linear(v).clamp(0., 1.);
+        Self {
+            forward_u8: (0..=u8::MAX)
+                .map(|v| to_linear(v as f32 / u8::MAX as f32))
+                .collect(),
+            forward_u16: (0..=u16::MAX)
+                .map(|v| to_linear(v as f32 / u16::MAX as f32))
+                .collect(),
+            backward_u8: (0..=u16::MAX)
+                .map(|v| (from_linear(v as f32 / u16::MAX as f32) * u8::MAX as f32).round() as u8)
+                .collect(),
+            backward_u16: (0..=u16::MAX)
+                .map(|v| (from_linear(v as f32 / u16::MAX as f32) * u16::MAX as f32).round() as u16)
+                .collect(),
+        }
+    }
+
+    /// Maps gamma-encoded components of source image into
+    /// linear components of destination image.
+    ///
+    /// # Safety
+    /// Source and destination images must have the same size.
+    pub unsafe fn forward_map<S, D, In, CC>(
+        &self,
+        src_image: &ImageView<S>,
+        dst_image: &mut ImageViewMut<D>,
+    ) where
+        In: MappableComponent,
+        CC: GetCount,
+        S: PixelExt<Component = In, CountOfComponents = CC>,
+        D: PixelExt<Component = u16, CountOfComponents = CC>,
+    {
+        check_sizes(src_image, dst_image);
+        let alpha_index = alpha_index::<S>();
+        let src_rows = src_image.iter_rows(0);
+        for (src_row, dst_row) in src_rows.zip(dst_image.iter_rows_mut()) {
+            let src_components = S::components(src_row);
+            let dst_components = D::components_mut(dst_row);
+            for (src_pixel, dst_pixel) in src_components
+                .chunks_exact(CC::count())
+                .zip(dst_components.chunks_exact_mut(CC::count()))
+            {
+                for (i, (&src, dst)) in src_pixel.iter().zip(dst_pixel).enumerate() {
+                    *dst = if Some(i) == alpha_index {
+                        src.into_component()
+                    } else {
+                        src.to_linear(self)
+                    };
+                }
+            }
+        }
+    }
+
+    /// Checked version of [`PixelComponentMapper::forward_map`].
+    pub fn try_forward_map<S, D, In, CC>(
+        &self,
+        src_image: &ImageView<S>,
+        dst_image: &mut ImageViewMut<D>,
+    ) -> Result<(), Error>
+    where
+        In: MappableComponent,
+        CC: GetCount,
+        S: PixelExt<Component = In, CountOfComponents = CC>,
+        D: PixelExt<Component = u16, CountOfComponents = CC>,
+    {
+        if src_image.width() != dst_image.width() || src_image.height() != dst_image.height() {
+            return Err(Error::SizeMismatch);
+        }
+        unsafe { self.forward_map(src_image, dst_image) };
+        Ok(())
+    }
+
+    /// Maps linear components of source image into
+    /// gamma-encoded components of destination image.
+    ///
+    /// # Safety
+    /// Source and destination images must have the same size.
+    pub unsafe fn backward_map<S, D, Out, CC>(
+        &self,
+        src_image: &ImageView<S>,
+        dst_image: &mut ImageViewMut<D>,
+    ) where
+        Out: MappableComponent,
+        CC: GetCount,
+        S: PixelExt<Component = u16, CountOfComponents = CC>,
+        D: PixelExt<Component = Out, CountOfComponents = CC>,
+    {
+        check_sizes(src_image, dst_image);
+        let alpha_index = alpha_index::<S>();
+        let src_rows = src_image.iter_rows(0);
+        for (src_row, dst_row) in src_rows.zip(dst_image.iter_rows_mut()) {
+            let src_components = S::components(src_row);
+            let dst_components = D::components_mut(dst_row);
+            for (src_pixel, dst_pixel) in src_components
+                .chunks_exact(CC::count())
+                .zip(dst_components.chunks_exact_mut(CC::count()))
+            {
+                for (i, (&src, dst)) in src_pixel.iter().zip(dst_pixel).enumerate() {
+                    *dst = if Some(i) == alpha_index {
+                        Out::from_u16(src)
+                    } else {
+                        Out::from_linear(src, self)
+                    };
+                }
+            }
+        }
+    }
+
+    /// Checked version of [`PixelComponentMapper::backward_map`].
+    pub fn try_backward_map<S, D, Out, CC>(
+        &self,
+        src_image: &ImageView<S>,
+        dst_image: &mut ImageViewMut<D>,
+    ) -> Result<(), Error>
+    where
+        Out: MappableComponent,
+        CC: GetCount,
+        S: PixelExt<Component = u16, CountOfComponents = CC>,
+        D: PixelExt<Component = Out, CountOfComponents = CC>,
+    {
+        if src_image.width() != dst_image.width() || src_image.height() != dst_image.height() {
+            return Err(Error::SizeMismatch);
+        }
+        unsafe { self.backward_map(src_image, dst_image) };
+        Ok(())
+    }
+}
+
+/// Type of gamma-encoded pixel component supported by [`PixelComponentMapper`].
+pub trait MappableComponent
+where
+    Self: PixelComponent + IntoPixelComponent<u16>,
+{
+    fn to_linear(self, mapper: &PixelComponentMapper) -> u16;
+    fn from_linear(value: u16, mapper: &PixelComponentMapper) -> Self;
+    /// Converts linear value without mapping.
+    fn from_u16(value: u16) -> Self;
+}
+
+impl MappableComponent for u8 {
+    #[inline(always)]
+    fn to_linear(self, mapper: &PixelComponentMapper) -> u16 {
+        mapper.forward_u8[self as usize]
+    }
+
+    #[inline(always)]
+    fn from_linear(value: u16, mapper: &PixelComponentMapper) -> Self {
+        mapper.backward_u8[value as usize]
+    }
+
+    #[inline(always)]
+    fn from_u16(value: u16) -> Self {
+        value.into_component()
+    }
+}
+
+impl MappableComponent for u16 {
+    #[inline(always)]
+    fn to_linear(self, mapper: &PixelComponentMapper) -> u16 {
+        mapper.forward_u16[self as usize]
+    }
+
+    #[inline(always)]
+    fn from_linear(value: u16, mapper: &PixelComponentMapper) -> Self {
+        mapper.backward_u16[value as usize]
+    }
+
+    #[inline(always)]
+    fn from_u16(value: u16) -> Self {
+        value
+    }
+}
+
+/// Returns index of alpha component of pixel.
+fn alpha_index<P: PixelExt>() -> Option<usize> {
+    match P::count_of_components() {
+        2 => Some(1),
+        4 => Some(3),
+        _ => None,
+    }
+}
+
+fn check_sizes<S: PixelExt, D: PixelExt>(src_image: &ImageView<S>, dst_image: &ImageViewMut<D>) {
+    if src_image.width() != dst_image.width() || src_image.height() != dst_image.height() {
+        error!(
+            "Size of source image ({}x{}) is not equal to size of destination image ({}x{})",
+            src_image.width(),
+            src_image.height(),
+            dst_image.width(),
+            dst_image.height()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use super::*;
+    use crate::pixels::{U16x2, U16x3, U16x4, U8x2, U8x3, U8x4};
+    use crate::{FilterType, Image, ResizeAlg, Resizer};
+
+    #[test]
+    fn test_srgb_round_trip() {
+        let width = NonZeroU32::new(256).unwrap();
+        let height = NonZeroU32::new(1).unwrap();
+        let buffer: Vec<u8> = (0..=255u8).flat_map(|v| [v, 255 - v]).collect();
+        let src_image = Image::<U8x2>::try_from_vec_u8(width, height, buffer.clone()).unwrap();
+        let mut linear_image = Image::<U16x2>::new(width, height);
+        let mut dst_image = Image::<U8x2>::new(width, height);
+
+        let mapper = mappers::create_srgb_mapper();
+        let mut linear_view = linear_image.try_view_mut().unwrap();
+        mapper
+            .try_forward_map(&src_image.try_view().unwrap(), &mut linear_view)
+            .unwrap();
+        let linear_view = linear_image.try_view().unwrap();
+        let linear_components = U16x2::components(linear_view.get_row(0).unwrap());
+        // sRGB value 128 is about 21.6% of linear light
+        assert_eq!(linear_components[128 * 2], 14_146);
+        // Alpha is mapped linearly
+        assert_eq!(linear_components[128 * 2 + 1], 127 * 257);
+
+        mapper
+            .try_backward_map(&linear_view, &mut dst_image.try_view_mut().unwrap())
+            .unwrap();
+        assert_eq!(dst_image.buffer(), buffer.as_slice());
+    }
+
+    /// Downscales 1-pixel checkerboard of black and white pixels 2:1
+    /// in linear light and returns components of resulting pixels.
+    fn downscale_checkerboard<S, L>(white: &[u8], black: &[u8]) -> Vec<u8>
+    where
+        S: PixelExt<Component = u8> + crate::Convolution,
+        L: PixelExt<Component = u16, CountOfComponents = S::CountOfComponents> + crate::Convolution,
+    {
+        let src_size = NonZeroU32::new(8).unwrap();
+        let dst_size = NonZeroU32::new(4).unwrap();
+        let buffer: Vec<u8> = (0..8 * 8)
+            .flat_map(|i| {
+                if (i / 8 + i % 8) % 2 == 0 {
+                    white
+                } else {
+                    black
+                }
+            })
+            .copied()
+            .collect();
+        let src_image = Image::<S>::try_from_vec_u8(src_size, src_size, buffer).unwrap();
+        let mut linear_src = Image::<L>::new(src_size, src_size);
+        let mut linear_dst = Image::<L>::new(dst_size, dst_size);
+        let mut dst_image = Image::<S>::new(dst_size, dst_size);
+
+        let mapper = mappers::create_srgb_mapper();
+        mapper
+            .try_forward_map(
+                &src_image.try_view().unwrap(),
+                &mut linear_src.try_view_mut().unwrap(),
+            )
+            .unwrap();
+        let mut resizer = Resizer::new(ResizeAlg::Convolution(FilterType::Box));
+        unsafe {
+            resizer.resize(
+                &linear_src.try_view().unwrap(),
+                &mut linear_dst.try_view_mut().unwrap(),
+            )
+        };
+        mapper
+            .try_backward_map(
+                &linear_dst.try_view().unwrap(),
+                &mut dst_image.try_view_mut().unwrap(),
+            )
+            .unwrap();
+        dst_image.into_vec()
+    }
+
+    #[test]
+    fn test_downscale_in_linear_light() {
+        // Half of linear light is about 188 in sRGB, while averaging
+        // of gamma-encoded values would give 128.
+        let result = downscale_checkerboard::<U8x3, U16x3>(&[255; 3], &[0; 3]);
+        assert!(result.iter().all(|&v| (187..=188).contains(&v)));
+
+        // Alpha channel is averaged linearly
+        let result = downscale_checkerboard::<U8x4, U16x4>(&[255; 4], &[0; 4]);
+        for pixel in result.chunks_exact(4) {
+            assert!(pixel[..3].iter().all(|&v| (187..=188).contains(&v)));
+            assert!((127..=128).contains(&pixel[3]));
+        }
+    }
+}
@@ -0,0 +1,250 @@
+use std::arch::x86_64::*;
+
+use crate::convolution::{Coefficients, CoefficientsChunk, FloatAccumulation};
+use crate::pixels::F32;
+use crate::{ImageView, ImageViewMut};
+
+#[inline]
+pub(crate) fn horiz_convolution(
+    src_image: &ImageView<F32>,
+    dst_image: &mut ImageViewMut<F32>,
+    offset: u32,
+    coeffs: Coefficients,
+) {
+    let src_rows = src_image.iter_rows(offset);
+    let dst_rows = dst_image.iter_rows_mut();
+    match coeffs.float_accumulation {
+        FloatAccumulation::F32 => {
+            let coefficients_chunks = coeffs.get_chunks_f32();
+            for (dst_row, src_row) in dst_rows.zip(src_rows) {
+                unsafe {
+                    horiz_convolution_row_f32(src_row, dst_row, &coefficients_chunks);
+                }
+            }
+        }
+        FloatAccumulation::F64 => {
+            let coefficients_chunks = coeffs.get_chunks();
+            for (dst_row, src_row) in dst_rows.zip(src_rows) {
+                unsafe {
+                    horiz_convolution_row(src_row, dst_row, &coefficients_chunks);
+                }
+            }
+        }
+    }
+}
+
+#[inline]
+pub(crate) fn vert_convolution(
+    src_image: &ImageView<F32>,
+    dst_image: &mut ImageViewMut<F32>,
+    offset: u32,
+    coeffs: Coefficients,
+) {
+    let dst_rows = dst_image.iter_rows_mut();
+    match coeffs.float_accumulation {
+        FloatAccumulation::F32 => {
+            let coefficients_chunks = coeffs.get_chunks_f32();
+            for (dst_row, coeffs_chunk) in dst_rows.zip(&coefficients_chunks) {
+                unsafe {
+                    vert_convolution_into_one_row_f32(
+                        src_image,
+                        dst_row,
+                        offset as usize,
+                        coeffs_chunk,
+                    );
+                }
+            }
+        }
+        FloatAccumulation::F64 => {
+            let coefficients_chunks = coeffs.get_chunks();
+            for (dst_row, coeffs_chunk) in dst_rows.zip(&coefficients_chunks) {
+                unsafe {
+                    vert_convolution_into_one_row(
+                        src_image,
+                        dst_row,
+                        offset as usize,
+                        coeffs_chunk,
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Loads 8 pixels and converts them into `f64` values.
+///
+/// For safety, it is necessary to ensure that `ptr` points
+/// to at least 8 pixels.
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn load_8_pixels(ptr: *const F32) -> (__m256d, __m256d) {
+    let pixels = _mm256_loadu_ps(ptr as *const f32);
+    (
+        _mm256_cvtps_pd(_mm256_castps256_ps128(pixels)),
+        _mm256_cvtps_pd(_mm256_extractf128_ps::<1>(pixels)),
+    )
+}
+
+/// For safety, it is necessary to ensure the following conditions:
+/// - coefficients_chunks.len() == dst_row.len()
+/// - max(chunk.start + chunk.values.len() for chunk in coefficients_chunks) <= src_row.len()
+#[target_feature(enable = "avx2")]
+unsafe fn horiz_convolution_row(
+    src_row: &[F32],
+    dst_row: &mut [F32],
+    coefficients_chunks: &[CoefficientsChunk],
+) {
+    for (dst_pixel, coeffs_chunk) in dst_row.iter_mut().zip(coefficients_chunks) {
+        let first_x_src = coeffs_chunk.start as usize;
+        let src_pixels = src_row.get_unchecked(first_x_src..);
+        let coeffs = coeffs_chunk.values;
+
+        let mut sum0 = _mm256_setzero_pd();
+        let mut sum1 = _mm256_setzero_pd();
+        let mut coeffs_chunks = coeffs.chunks_exact(8);
+        for (k, pixels) in (&mut coeffs_chunks).zip(src_pixels.chunks_exact(8)) {
+            let (pixels0, pixels1) = load_8_pixels(pixels.as_ptr());
+            sum0 = _mm256_add_pd(sum0, _mm256_mul_pd(pixels0, _mm256_loadu_pd(k.as_ptr())));
+            sum1 = _mm256_add_pd(
+                sum1,
+                _mm256_mul_pd(pixels1, _mm256_loadu_pd(k.as_ptr().add(4))),
+            );
+        }
+        let mut ss = horizontal_sum(sum0, sum1);
+
+        let reminder = coeffs_chunks.remainder();
+        let src_pixels = src_pixels.get_unchecked(coeffs.len() - reminder.len()..);
+        for (&k, &pixel) in reminder.iter().zip(src_pixels) {
+            ss += pixel.0 as f64 * k;
+        }
+        dst_pixel.0 = ss as f32;
+    }
+}
+
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn horizontal_sum(sum0: __m256d, sum1: __m256d) -> f64 {
+    let sum = _mm256_add_pd(sum0, sum1);
+    let sum = _mm_add_pd(_mm256_castpd256_pd128(sum), _mm256_extractf128_pd::<1>(sum));
+    _mm_cvtsd_f64(sum) + _mm_cvtsd_f64(_mm_unpackhi_pd(sum, sum))
+}
+
+/// For safety, it is necessary to ensure the following conditions:
+/// - src_x + dst_row.len() <= src_image.width()
+/// - coeffs_chunk.start + coeffs_chunk.values.len() <= src_image.height()
+#[target_feature(enable = "avx2")]
+unsafe fn vert_convolution_into_one_row(
+    src_image: &ImageView<F32>,
+    dst_row: &mut [F32],
+    mut src_x: usize,
+    coeffs_chunk: &CoefficientsChunk,
+) {
+    let y_start = coeffs_chunk.start;
+    let coeffs = coeffs_chunk.values;
+    let mut buf = [0f64; 8];
+
+    let mut dst_chunks = dst_row.chunks_exact_mut(8);
+    for dst_pixels in &mut dst_chunks {
+        // Every lane accumulates values of its own pixel in the same
+        // order as native implementation does.
+        let mut sum0 = _mm256_setzero_pd();
+        let mut sum1 = _mm256_setzero_pd();
+        for (src_row, &k) in src_image.iter_rows(y_start).zip(coeffs) {
+            let k = _mm256_set1_pd(k);
+            let (pixels0, pixels1) = load_8_pixels(src_row.get_unchecked(src_x..).as_ptr());
+            sum0 = _mm256_add_pd(sum0, _mm256_mul_pd(pixels0, k));
+            sum1 = _mm256_add_pd(sum1, _mm256_mul_pd(pixels1, k));
+        }
+        _mm256_storeu_pd(buf.as_mut_ptr(), sum0);
+        _mm256_storeu_pd(buf.as_mut_ptr().add(4), sum1);
+        for (dst_pixel, &ss) in dst_pixels.iter_mut().zip(&buf) {
+            dst_pixel.0 = ss as f32;
+        }
+        src_x += 8;
+    }
+
+    for dst_pixel in dst_chunks.into_remainder() {
+        let mut ss = 0.;
+        for (src_row, &k) in src_image.iter_rows(y_start).zip(coeffs) {
+            let src_pixel = src_row.get_unchecked(src_x);
+            ss += src_pixel.0 as f64 * k;
+        }
+        dst_pixel.0 = ss as f32;
+        src_x += 1;
+    }
+}
+
+/// For safety, it is necessary to ensure the following conditions:
+/// - coefficients_chunks.len() == dst_row.len()
+/// - max(chunk.start + chunk.values.len() for chunk in coefficients_chunks) <= src_row.len()
+#[target_feature(enable = "avx2")]
+unsafe fn horiz_convolution_row_f32(
+    src_row: &[F32],
+    dst_row: &mut [F32],
+    coefficients_chunks: &[CoefficientsChunk<f32>],
+) {
+    for (dst_pixel, coeffs_chunk) in dst_row.iter_mut().zip(coefficients_chunks) {
+        let first_x_src = coeffs_chunk.start as usize;
+        let src_pixels = src_row.get_unchecked(first_x_src..);
+        let coeffs = coeffs_chunk.values;
+
+        let mut sum = _mm256_setzero_ps();
+        let mut coeffs_chunks = coeffs.chunks_exact(8);
+        for (k, pixels) in (&mut coeffs_chunks).zip(src_pixels.chunks_exact(8)) {
+            let pixels = _mm256_loadu_ps(pixels.as_ptr() as *const f32);
+            sum = _mm256_add_ps(sum, _mm256_mul_ps(pixels, _mm256_loadu_ps(k.as_ptr())));
+        }
+        let mut ss = horizontal_sum_f32(sum);
+
+        let reminder = coeffs_chunks.remainder();
+        let src_pixels = src_pixels.get_unchecked(coeffs.len() - reminder.len()..);
+        for (&k, &pixel) in reminder.iter().zip(src_pixels) {
+            ss += pixel.0 * k;
+        }
+        dst_pixel.0 = ss;
+    }
+}
+
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn horizontal_sum_f32(sum: __m256) -> f32 {
+    let sum = _mm_add_ps(_mm256_castps256_ps128(sum), _mm256_extractf128_ps::<1>(sum));
+    let sum = _mm_add_ps(sum, _mm_movehl_ps(sum, sum));
+    let sum = _mm_add_ss(sum, _mm_shuffle_ps::<0b01>(sum, sum));
+    _mm_cvtss_f32(sum)
+}
+
+/// For safety, it is necessary to ensure the following conditions:
+/// - src_x + dst_row.len() <= src_image.width()
+/// - coeffs_chunk.start + coeffs_chunk.values.len() <= src_image.height()
+#[target_feature(enable = "avx2")]
+unsafe fn vert_convolution_into_one_row_f32(
+    src_image: &ImageView<F32>,
+    dst_row: &mut [F32],
+    mut src_x: usize,
+    coeffs_chunk: &CoefficientsChunk<f32>,
+) {
+    let y_start = coeffs_chunk.start;
+    let coeffs = coeffs_chunk.values;
+
+    let mut dst_chunks = dst_row.chunks_exact_mut(8);
+    for dst_pixels in &mut dst_chunks {
+        let mut sum = _mm256_setzero_ps();
+        for (src_row, &k) in src_image.iter_rows(y_start).zip(coeffs) {
+            let pixels = _mm256_loadu_ps(src_row.get_unchecked(src_x..).as_ptr() as *const f32);
+            sum = _mm256_add_ps(sum, _mm256_mul_ps(pixels, _mm256_set1_ps(k)));
+        }
+        _mm256_storeu_ps(dst_pixels.as_mut_ptr() as *mut f32, sum);
+        src_x += 8;
+    }
+
+    for dst_pixel in dst_chunks.into_remainder() {
+        let mut ss = 0.;
+        for (src_row, &k) in src_image.iter_rows(y_start).zip(coeffs) {
+            let src_pixel = src_row.get_unchecked(src_x);
+            ss += src_pixel.0 * k;
+        }
+        dst_pixel.0 = ss;
+        src_x += 1;
+    }
+}
@@ -2,9 +2,17 @@ use crate::pixels::F32;
 use crate::CpuExtensions;
 use crate::{ImageView, ImageViewMut};
 
-use super::{Coefficients, Convolution};
+use super::{check_horiz_convolution, check_vert_convolution, Coefficients, Convolution};
 
+#[cfg(target_arch = "x86_64")]
+mod avx2;
 mod native;
+#[cfg(target_arch = "aarch64")]
+mod neon;
+#[cfg(target_arch = "x86_64")]
+mod sse4;
+#[cfg(target_arch = "wasm32")]
+mod wasm32;
 
 impl Convolution for F32 {
     fn horiz_convolution(
@@ -12,9 +20,22 @@ impl Convolution for F32 {
         dst_image: &mut ImageViewMut<Self>,
         offset: u32,
         coeffs: Coefficients,
-        _cpu_extensions: CpuExtensions,
+        cpu_extensions: CpuExtensions,
     ) {
-        native::horiz_convolution(src_image, dst_image, offset, coeffs);
+        check_horiz_convolution(src_image, dst_image, offset, &coeffs);
+        match cpu_extensions {
+            #[cfg(target_arch = "x86_64")]
+            CpuExtensions::Avx2 => avx2::horiz_convolution(src_image, dst_image, offset, coeffs),
+            #[cfg(target_arch = "x86_64")]
+            CpuExtensions::Sse4_1 => sse4::horiz_convolution(src_image, dst_image, offset, coeffs),
+            #[cfg(target_arch = "aarch64")]
+            CpuExtensions::Neon => neon::horiz_convolution(src_image, dst_image, offset, coeffs),
+            #[cfg(target_arch = "wasm32")]
+            CpuExtensions::Simd128 => {
+                wasm32::horiz_convolution(src_image, dst_image, offset, coeffs)
+            }
+            _ => native::horiz_convolution(src_image, dst_image, offset, coeffs),
+        }
     }
 
     fn vert_convolution(
@@ -22,8 +43,21 @@ impl Convolution for F32 {
         dst_image: &mut ImageViewMut<Self>,
         offset: u32,
         coeffs: Coefficients,
-        _cpu_extensions: CpuExtensions,
+        cpu_extensions: CpuExtensions,
     ) {
-        native::vert_convolution(src_image, dst_image, offset, coeffs);
+        check_vert_convolution(src_image, dst_image, offset, &coeffs);
+        match cpu_extensions {
+            #[cfg(target_arch = "x86_64")]
+            CpuExtensions::Avx2 => avx2::vert_convolution(src_image, dst_image, offset, coeffs),
+            #[cfg(target_arch = "x86_64")]
+            CpuExtensions::Sse4_1 => sse4::vert_convolution(src_image, dst_image, offset, coeffs),
+            #[cfg(target_arch = "aarch64")]
+            CpuExtensions::Neon => neon::vert_convolution(src_image, dst_image, offset, coeffs),
+            #[cfg(target_arch = "wasm32")]
+            CpuExtensions::Simd128 => {
+                wasm32::vert_convolution(src_image, dst_image, offset, coeffs)
+            }
+            _ => native::vert_convolution(src_image, dst_image, offset, coeffs),
+        }
     }
 }
@@ -1,4 +1,4 @@
-use crate::convolution::Coefficients;
+use crate::convolution::{Coefficients, FloatAccumulation, FloatAccumulator};
 use crate::pixels::F32;
 use crate::{ImageView, ImageViewMut};
 
@@ -8,18 +8,34 @@ pub(crate) fn horiz_convolution(
     offset: u32,
     coeffs: Coefficients,
 ) {
-    let coefficients_chunks = coeffs.get_chunks();
+    match coeffs.float_accumulation {
+        FloatAccumulation::F32 => {
+            horiz_convolution_with::<f32>(src_image, dst_image, offset, &coeffs)
+        }
+        FloatAccumulation::F64 => {
+            horiz_convolution_with::<f64>(src_image, dst_image, offset, &coeffs)
+        }
+    }
+}
+
+fn horiz_convolution_with<T: FloatAccumulator>(
+    src_image: &ImageView<F32>,
+    dst_image: &mut ImageViewMut<F32>,
+    offset: u32,
+    coeffs: &Coefficients,
+) {
+    let coefficients_chunks = T::get_chunks(coeffs);
     let src_rows = src_image.iter_rows(offset);
     let dst_rows = dst_image.iter_rows_mut();
     for (dst_row, src_row) in dst_rows.zip(src_rows) {
         for (dst_pixel, coeffs_chunk) in dst_row.iter_mut().zip(&coefficients_chunks) {
             let first_x_src = coeffs_chunk.start as usize;
-            let mut ss = 0.;
+            let mut ss = T::default();
             let src_pixels = unsafe { src_row.get_unchecked(first_x_src..) };
             for (&k, &pixel) in coeffs_chunk.values.iter().zip(src_pixels) {
-                ss += pixel.0 as f64 * k;
+                ss += T::from_f32(pixel.0) * k;
             }
-            dst_pixel.0 = ss.round() as f32;
+            dst_pixel.0 = ss.to_f32();
         }
     }
 }
@@ -30,21 +46,35 @@ pub(crate) fn vert_convolution(
     offset: u32,
     coeffs: Coefficients,
 ) {
-    let coefficients_chunks = coeffs.get_chunks();
+    match coeffs.float_accumulation {
+        FloatAccumulation::F32 => {
+            vert_convolution_with::<f32>(src_image, dst_image, offset, &coeffs)
+        }
+        FloatAccumulation::F64 => {
+            vert_convolution_with::<f64>(src_image, dst_image, offset, &coeffs)
+        }
+    }
+}
+
+fn vert_convolution_with<T: FloatAccumulator>(
+    src_image: &ImageView<F32>,
+    dst_image: &mut ImageViewMut<F32>,
+    offset: u32,
+    coeffs: &Coefficients,
+) {
+    let coefficients_chunks = T::get_chunks(coeffs);
     let dst_rows = dst_image.iter_rows_mut();
     let start_src_x = offset as usize;
     for (&coeffs_chunk, dst_row) in coefficients_chunks.iter().zip(dst_rows) {
         let first_y_src = coeffs_chunk.start;
-        let mut src_x = start_src_x;
-        for dst_pixel in dst_row.iter_mut() {
-            let mut ss = 0.;
+        for (src_x, dst_pixel) in (start_src_x..).zip(dst_row.iter_mut()) {
+            let mut ss = T::default();
             let src_rows = src_image.iter_rows(first_y_src);
             for (src_row, &k) in src_rows.zip(coeffs_chunk.values) {
                 let src_pixel = unsafe { src_row.get_unchecked(src_x) };
-                ss += src_pixel.0 as f64 * k;
+                ss += T::from_f32(src_pixel.0) * k;
             }
-            dst_pixel.0 = ss.round() as f32;
-            src_x += 1;
+            dst_pixel.0 = ss.to_f32();
         }
     }
 }
@@ -0,0 +1,84 @@
+use crate::convolution::{Coefficients, FloatAccumulation, FloatAccumulator};
+use crate::pixels::F32x2;
+use crate::{ImageView, ImageViewMut};
+
+pub(crate) fn horiz_convolution(
+    src_image: &ImageView<F32x2>,
+    dst_image: &mut ImageViewMut<F32x2>,
+    offset: u32,
+    coeffs: Coefficients,
+) {
+    match coeffs.float_accumulation {
+        FloatAccumulation::F32 => {
+            horiz_convolution_with::<f32>(src_image, dst_image, offset, &coeffs)
+        }
+        FloatAccumulation::F64 => {
+            horiz_convolution_with::<f64>(src_image, dst_image, offset, &coeffs)
+        }
+    }
+}
+
+fn horiz_convolution_with<T: FloatAccumulator>(
+    src_image: &ImageView<F32x2>,
+    dst_image: &mut ImageViewMut<F32x2>,
+    offset: u32,
+    coeffs: &Coefficients,
+) {
+    let coefficients_chunks = T::get_chunks(coeffs);
+    let src_rows = src_image.iter_rows(offset);
+    let dst_rows = dst_image.iter_rows_mut();
+    for (dst_row, src_row) in dst_rows.zip(src_rows) {
+        for (dst_pixel, coeffs_chunk) in dst_row.iter_mut().zip(&coefficients_chunks) {
+            let first_x_src = coeffs_chunk.start as usize;
+            let mut ss = [T::default(); 2];
+            let src_pixels = unsafe { src_row.get_unchecked(first_x_src..) };
+            for (&k, src_pixel) in coeffs_chunk.values.iter().zip(src_pixels) {
+                for (s, &c) in ss.iter_mut().zip(src_pixel.0.iter()) {
+                    *s += T::from_f32(c) * k;
+                }
+            }
+            dst_pixel.0 = ss.map(T::to_f32);
+        }
+    }
+}
+
+pub(crate) fn vert_convolution(
+    src_image: &ImageView<F32x2>,
+    dst_image: &mut ImageViewMut<F32x2>,
+    offset: u32,
+    coeffs: Coefficients,
+) {
+    match coeffs.float_accumulation {
+        FloatAccumulation::F32 => {
+            vert_convolution_with::<f32>(src_image, dst_image, offset, &coeffs)
+        }
+        FloatAccumulation::F64 => {
+            vert_convolution_with::<f64>(src_image, dst_image, offset, &coeffs)
+        }
+    }
+}
+
+fn vert_convolution_with<T: FloatAccumulator>(
+    src_image: &ImageView<F32x2>,
+    dst_image: &mut ImageViewMut<F32x2>,
+    offset: u32,
+    coeffs: &Coefficients,
+) {
+    let coefficients_chunks = T::get_chunks(coeffs);
+    let dst_rows = dst_image.iter_rows_mut();
+    let start_src_x = offset as usize;
+    for (&coeffs_chunk, dst_row) in coefficients_chunks.iter().zip(dst_rows) {
+        let first_y_src = coeffs_chunk.start;
+        for (src_x, dst_pixel) in (start_src_x..).zip(dst_row.iter_mut()) {
+            let mut ss = [T::default(); 2];
+            let src_rows = src_image.iter_rows(first_y_src);
+            for (src_row, &k) in src_rows.zip(coeffs_chunk.values) {
+                let src_pixel = unsafe { src_row.get_unchecked(src_x) };
+                for (s, &c) in ss.iter_mut().zip(src_pixel.0.iter()) {
+                    *s += T::from_f32(c) * k;
+                }
+            }
+            dst_pixel.0 = ss.map(T::to_f32);
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use crate::pixels::F32x3;
+use crate::CpuExtensions;
+use crate::{ImageView, ImageViewMut};
+
+use super::{check_horiz_convolution, check_vert_convolution, Coefficients, Convolution};
+
+mod native;
+
+impl Convolution for F32x3 {
+    fn horiz_convolution(
+        src_image: &ImageView<Self>,
+        dst_image: &mut ImageViewMut<Self>,
+        offset: u32,
+        coeffs: Coefficients,
+        _cpu_extensions: CpuExtensions,
+    ) {
+        check_horiz_convolution(src_image, dst_image, offset, &coeffs);
+        native::horiz_convolution(src_image, dst_image, offset, coeffs);
+    }
+
+    fn vert_convolution(
+        src_image: &ImageView<Self>,
+        dst_image: &mut ImageViewMut<Self>,
+        offset: u32,
+        coeffs: Coefficients,
+        _cpu_extensions: CpuExtensions,
+    ) {
+        check_vert_convolution(src_image, dst_image, offset, &coeffs);
+        native::vert_convolution(src_image, dst_image, offset, coeffs);
+    }
+}
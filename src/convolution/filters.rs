@@ -1,8 +1,15 @@
 use std::f64::consts::PI;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
 
-pub type FilterFn<'a> = &'a dyn Fn(f64) -> f64;
+use crate::Error;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub type FilterFn<'a> = Box<dyn Fn(f64) -> f64 + 'a>;
+
+/// Parameters of filters are compared by their bit patterns, so `FilterType`
+/// implements [`Eq`]. It is not [`Copy`] because [`FilterType::Custom`]
+/// holds a shared user-defined function, use [`Clone`] instead.
+#[derive(Clone, Debug)]
 #[non_exhaustive]
 pub enum FilterType {
     /// Each pixel of source image contributes to one pixel of the
@@ -30,24 +37,190 @@ pub enum FilterType {
     /// Lanczos filter (a truncated sinc) on all pixels that may contribute
     /// to the output value.
     Lanczos3,
+    /// Lanczos filter with 2 lobes. It is sharper than `Bilinear`
+    /// and has less ringing than `Lanczos3`.
+    Lanczos2,
+    /// Lanczos filter with 4 lobes. It is sharper than `Lanczos3`,
+    /// but has more ringing.
+    Lanczos4,
+    /// Gaussian filter with given standard deviation `sigma`, which must
+    /// be a finite positive value. The filter is truncated at `3 * sigma`.
+    /// Produces a soft image without ringing.
+    Gaussian { sigma: f64 },
+    /// Spline16 filter (support 2) which approximates Lanczos2
+    /// with piecewise cubic polynomials.
+    Spline16,
+    /// Spline36 filter (support 3) which approximates Lanczos3
+    /// with piecewise cubic polynomials.
+    Spline36,
+    /// Spline64 filter (support 4) which approximates Lanczos4
+    /// with piecewise cubic polynomials.
+    Spline64,
+    /// Cubic B-spline filter (B = 1, C = 0). Produces a very smooth
+    /// image without ringing.
+    BSpline,
+    /// Sinc filter with 3 lobes windowed by Blackman window.
+    Blackman,
+    /// Cubic filter from the family of Mitchell–Netravali filters with
+    /// given parameters `b` and `c`, which must be finite values.
+    /// For example:
+    /// - Hermite: `b = 0, c = 0`;
+    /// - B-spline: `b = 1, c = 0`;
+    /// - Catmull-Rom: `b = 0, c = 0.5`;
+    /// - Mitchell: `b = 1/3, c = 1/3`;
+    /// - Robidoux: `b = 0.37821575509399867, c = 0.31089212245300067`;
+    /// - Robidoux Sharp: `b = 0.2620145123990142, c = 0.3689927438004929`.
+    ///
+    /// Lesser values of `b` and greater values of `c` produce a sharper image.
+    Cubic { b: f64, c: f64 },
+    /// Sinc filter with 3 lobes windowed by Kaiser window with
+    /// given shape parameter `beta`, which must be a finite
+    /// non-negative value.
+    Kaiser { beta: f64 },
+    /// User-defined filter function with its support.
+    Custom(Filter),
+}
+
+impl PartialEq for FilterType {
+    fn eq(&self, other: &Self) -> bool {
+        let eq = |a: f64, b: f64| a.to_bits() == b.to_bits();
+        match (self, other) {
+            (Self::Gaussian { sigma: a }, Self::Gaussian { sigma: b }) => eq(*a, *b),
+            (Self::Cubic { b: b1, c: c1 }, Self::Cubic { b: b2, c: c2 }) => {
+                eq(*b1, *b2) && eq(*c1, *c2)
+            }
+            (Self::Kaiser { beta: a }, Self::Kaiser { beta: b }) => eq(*a, *b),
+            (Self::Custom(a), Self::Custom(b)) => a == b,
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
 }
 
+impl Eq for FilterType {}
+
 impl Default for FilterType {
     fn default() -> Self {
         FilterType::Lanczos3
     }
 }
 
-/// Returns reference to filter function and value of `filter_support`.
+/// User-defined filter function.
+///
+/// Filters are equal only if they are clones of the same instance
+/// created by [`Filter::new`] or [`Filter::try_new`].
+///
+/// ```
+/// use fer::{Filter, FilterType, ResizeAlg};
+///
+/// let triangle = Filter::try_new(|x: f64| (1. - x.abs()).max(0.), 1.).unwrap();
+/// let algorithm = ResizeAlg::Convolution(FilterType::Custom(triangle));
+/// ```
+#[derive(Clone)]
+pub struct Filter {
+    func: Arc<dyn Fn(f64) -> f64 + Send + Sync>,
+    support: f64,
+}
+
+impl Filter {
+    /// Creates filter from a function (closure or trait object) and
+    /// its support. The function must return zero for all values
+    /// outside of the range `-support..=support` and finite values
+    /// inside of it. Non-finite values are used as zero weights.
+    ///
+    /// # Panics
+    /// Panics if the `support` is not a finite positive value.
+    /// Use [`Filter::try_new`] to get an error instead.
+    pub fn new<F>(func: F, support: f64) -> Self
+    where
+        F: Fn(f64) -> f64 + Send + Sync + 'static,
+    {
+        match Self::try_new(func, support) {
+            Ok(filter) => filter,
+            Err(_) => panic!("Support of filter ({support}) is not a finite positive value"),
+        }
+    }
+
+    /// Checked version of [`Filter::new`].
+    pub fn try_new<F>(func: F, support: f64) -> Result<Self, Error>
+    where
+        F: Fn(f64) -> f64 + Send + Sync + 'static,
+    {
+        if !Self::is_valid_support(support) {
+            return Err(Error::InvalidFilterSupport);
+        }
+        Ok(Self {
+            func: Arc::new(func),
+            support,
+        })
+    }
+
+    #[inline(always)]
+    fn is_valid_support(support: f64) -> bool {
+        support.is_finite() && support > 0.
+    }
+
+    #[inline(always)]
+    pub fn support(&self) -> f64 {
+        self.support
+    }
+}
+
+impl Debug for Filter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Filter")
+            .field("func", &Arc::as_ptr(&self.func))
+            .field("support", &self.support)
+            .finish()
+    }
+}
+
+impl PartialEq for Filter {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.func, &other.func) && self.support.to_bits() == other.support.to_bits()
+    }
+}
+
+impl Eq for Filter {}
+
+impl FilterType {
+    /// Returns `true` if parameters of filter are valid.
+    pub fn is_valid(&self) -> bool {
+        match *self {
+            Self::Gaussian { sigma } => sigma.is_finite() && sigma > 0.,
+            Self::Kaiser { beta } => beta.is_finite() && beta >= 0.,
+            Self::Cubic { b, c } => b.is_finite() && c.is_finite(),
+            _ => true,
+        }
+    }
+}
+
+/// Returns filter function and value of `filter_support`.
 #[inline]
-pub fn get_filter_func(filter_type: FilterType) -> (FilterFn<'static>, f64) {
-    match filter_type {
-        FilterType::Box => (&box_filter, 0.5),
-        FilterType::Bilinear => (&bilinear_filter, 1.0),
-        FilterType::Hamming => (&hamming_filter, 1.0),
-        FilterType::CatmullRom => (&catmul_filter, 2.0),
-        FilterType::Mitchell => (&mitchell_filter, 2.0),
-        FilterType::Lanczos3 => (&lanczos_filter, 3.0),
+pub fn get_filter_func(filter_type: &FilterType) -> (FilterFn<'_>, f64) {
+    match *filter_type {
+        FilterType::Box => (Box::new(box_filter), 0.5),
+        FilterType::Bilinear => (Box::new(bilinear_filter), 1.0),
+        FilterType::Hamming => (Box::new(hamming_filter), 1.0),
+        FilterType::CatmullRom => (Box::new(catmul_filter), 2.0),
+        FilterType::Mitchell => (Box::new(mitchell_filter), 2.0),
+        FilterType::Lanczos3 => (Box::new(|x| lanczos_filter(x, 3.0)), 3.0),
+        FilterType::Lanczos2 => (Box::new(|x| lanczos_filter(x, 2.0)), 2.0),
+        FilterType::Lanczos4 => (Box::new(|x| lanczos_filter(x, 4.0)), 4.0),
+        FilterType::Gaussian { sigma } => {
+            (Box::new(move |x| gaussian_filter(x, sigma)), 3.0 * sigma)
+        }
+        FilterType::Spline16 => (Box::new(spline16_filter), 2.0),
+        FilterType::Spline36 => (Box::new(spline36_filter), 3.0),
+        FilterType::Spline64 => (Box::new(spline64_filter), 4.0),
+        FilterType::BSpline => (Box::new(bspline_filter), 2.0),
+        FilterType::Blackman => (Box::new(blackman_filter), 3.0),
+        FilterType::Cubic { b, c } => (Box::new(move |x| cubic_filter(x, b, c)), 2.0),
+        FilterType::Kaiser { beta } => {
+            // Denominator of window is calculated once
+            let i0_beta = bessel_i0(beta);
+            (Box::new(move |x| kaiser_filter(x, beta, i0_beta)), 3.0)
+        }
+        FilterType::Custom(ref filter) => (Box::new(&*filter.func), filter.support),
     }
 }
 
@@ -112,6 +285,25 @@ fn mitchell_filter(mut x: f64) -> f64 {
     }
 }
 
+/// Mitchell–Netravali filter with given B and C
+/// https://en.wikipedia.org/wiki/Mitchell%E2%80%93Netravali_filters
+#[inline]
+fn cubic_filter(mut x: f64, b: f64, c: f64) -> f64 {
+    x = x.abs();
+    if x < 1.0 {
+        ((12. - 9. * b - 6. * c) * x * x * x + (-18. + 12. * b + 6. * c) * x * x + (6. - 2. * b))
+            / 6.
+    } else if x < 2.0 {
+        ((-b - 6. * c) * x * x * x
+            + (6. * b + 30. * c) * x * x
+            + (-12. * b - 48. * c) * x
+            + (8. * b + 24. * c))
+            / 6.
+    } else {
+        0.0
+    }
+}
+
 #[inline]
 fn sinc_filter(mut x: f64) -> f64 {
     if x == 0.0 {
@@ -123,11 +315,221 @@ fn sinc_filter(mut x: f64) -> f64 {
 }
 
 #[inline]
-fn lanczos_filter(x: f64) -> f64 {
+fn lanczos_filter(x: f64, lobes: f64) -> f64 {
     // truncated sinc
-    if (-3.0..3.0).contains(&x) {
-        sinc_filter(x) * sinc_filter(x / 3.)
+    if (-lobes..lobes).contains(&x) {
+        sinc_filter(x) * sinc_filter(x / lobes)
+    } else {
+        0.0
+    }
+}
+
+#[inline]
+fn gaussian_filter(x: f64, sigma: f64) -> f64 {
+    (-x * x / (2. * sigma * sigma)).exp()
+}
+
+/// Spline16 filter
+/// http://avisynth.nl/index.php/Resize
+#[inline]
+fn spline16_filter(mut x: f64) -> f64 {
+    x = x.abs();
+    if x < 1.0 {
+        ((x - 9. / 5.) * x - 1. / 5.) * x + 1.
+    } else if x < 2.0 {
+        x -= 1.;
+        ((-1. / 3. * x + 4. / 5.) * x - 7. / 15.) * x
     } else {
         0.0
     }
 }
+
+/// Spline36 filter
+/// http://avisynth.nl/index.php/Resize
+#[inline]
+fn spline36_filter(mut x: f64) -> f64 {
+    x = x.abs();
+    if x < 1.0 {
+        ((13. / 11. * x - 453. / 209.) * x - 3. / 209.) * x + 1.
+    } else if x < 2.0 {
+        x -= 1.;
+        ((-6. / 11. * x + 270. / 209.) * x - 156. / 209.) * x
+    } else if x < 3.0 {
+        x -= 2.;
+        ((1. / 11. * x - 45. / 209.) * x + 26. / 209.) * x
+    } else {
+        0.0
+    }
+}
+
+/// Spline64 filter
+/// http://avisynth.nl/index.php/Resize
+#[inline]
+fn spline64_filter(mut x: f64) -> f64 {
+    x = x.abs();
+    if x < 1.0 {
+        ((49. / 41. * x - 6387. / 2911.) * x - 3. / 2911.) * x + 1.
+    } else if x < 2.0 {
+        x -= 1.;
+        ((-24. / 41. * x + 4032. / 2911.) * x - 2328. / 2911.) * x
+    } else if x < 3.0 {
+        x -= 2.;
+        ((6. / 41. * x - 1008. / 2911.) * x + 582. / 2911.) * x
+    } else if x < 4.0 {
+        x -= 3.;
+        ((-1. / 41. * x + 168. / 2911.) * x - 97. / 2911.) * x
+    } else {
+        0.0
+    }
+}
+
+/// Cubic B-spline filter (B = 1, C = 0)
+/// https://en.wikipedia.org/wiki/Mitchell%E2%80%93Netravali_filters
+#[inline]
+fn bspline_filter(mut x: f64) -> f64 {
+    x = x.abs();
+    if x < 1.0 {
+        (0.5 * x - 1.) * x * x + 2. / 3.
+    } else if x < 2.0 {
+        x = 2. - x;
+        x * x * x / 6.
+    } else {
+        0.0
+    }
+}
+
+/// Sinc filter windowed by Blackman window
+/// https://en.wikipedia.org/wiki/Window_function#Blackman_window
+#[inline]
+fn blackman_filter(x: f64) -> f64 {
+    const LOBES: f64 = 3.0;
+    if (-LOBES..LOBES).contains(&x) {
+        let t = PI * x / LOBES;
+        sinc_filter(x) * (0.42 + 0.5 * t.cos() + 0.08 * (2. * t).cos())
+    } else {
+        0.0
+    }
+}
+
+/// Sinc filter windowed by Kaiser window
+/// https://en.wikipedia.org/wiki/Kaiser_window
+#[inline]
+fn kaiser_filter(x: f64, beta: f64, i0_beta: f64) -> f64 {
+    const LOBES: f64 = 3.0;
+    if (-LOBES..LOBES).contains(&x) {
+        let t = x / LOBES;
+        sinc_filter(x) * bessel_i0(beta * (1. - t * t).sqrt()) / i0_beta
+    } else {
+        0.0
+    }
+}
+
+/// Modified Bessel function of the first kind of order zero.
+fn bessel_i0(x: f64) -> f64 {
+    let quarter_x2 = x * x / 4.;
+    let mut sum = 1.0;
+    let mut term = 1.0;
+    let mut k = 1.0;
+    // Terms of the series decrease quickly for values
+    // of `beta` used by Kaiser window.
+    while term > sum * f64::EPSILON {
+        term *= quarter_x2 / (k * k);
+        sum += term;
+        k += 1.;
+    }
+    sum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interpolating_filters() {
+        let filters = [
+            FilterType::Lanczos2,
+            FilterType::Lanczos4,
+            FilterType::Spline16,
+            FilterType::Spline36,
+            FilterType::Spline64,
+            FilterType::Blackman,
+            FilterType::Kaiser { beta: 4. },
+        ];
+        for filter_type in filters {
+            let (filter_fn, support) = get_filter_func(&filter_type);
+            assert!((filter_fn(0.) - 1.).abs() < 1e-12, "{:?}", filter_type);
+            for x in 1..=support as i32 {
+                let x = x as f64;
+                assert!(filter_fn(x).abs() < 1e-12, "{:?}", filter_type);
+                assert!(filter_fn(-x).abs() < 1e-12, "{:?}", filter_type);
+            }
+            assert_eq!(filter_fn(support + 0.1), 0.);
+        }
+    }
+
+    #[test]
+    fn test_cubic_filter() {
+        let pairs = [
+            (FilterType::Cubic { b: 0., c: 0.5 }, FilterType::CatmullRom),
+            (
+                FilterType::Cubic {
+                    b: 1. / 3.,
+                    c: 1. / 3.,
+                },
+                FilterType::Mitchell,
+            ),
+            (FilterType::Cubic { b: 1., c: 0. }, FilterType::BSpline),
+        ];
+        for (cubic, expected) in pairs {
+            let (cubic_fn, cubic_support) = get_filter_func(&cubic);
+            let (expected_fn, expected_support) = get_filter_func(&expected);
+            assert_eq!(cubic_support, expected_support);
+            for i in -25..=25 {
+                let x = i as f64 / 10.;
+                assert!((cubic_fn(x) - expected_fn(x)).abs() < 1e-12, "{:?}", cubic);
+            }
+        }
+        assert!(!FilterType::Cubic { b: f64::NAN, c: 0. }.is_valid());
+    }
+
+    #[test]
+    fn test_bessel_i0() {
+        // Values from tables of modified Bessel functions
+        assert_eq!(bessel_i0(0.), 1.);
+        assert!((bessel_i0(1.) - 1.2660658777520082).abs() < 1e-12);
+        assert!((bessel_i0(5.) - 27.239871823604442).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_custom_filter_support() {
+        let triangle = |x: f64| (1. - x.abs()).max(0.);
+        assert_eq!(Filter::new(triangle, 1.).support(), 1.);
+        for support in [0., -1., f64::NAN, f64::INFINITY] {
+            assert_eq!(
+                Filter::try_new(triangle, support).unwrap_err(),
+                Error::InvalidFilterSupport
+            );
+            let result = std::panic::catch_unwind(|| Filter::new(triangle, support));
+            assert!(result.is_err());
+        }
+    }
+
+    #[test]
+    fn test_filter_type_eq() {
+        let gaussian = FilterType::Gaussian { sigma: f64::NAN };
+        assert_eq!(gaussian, gaussian.clone());
+        assert_ne!(gaussian, FilterType::Gaussian { sigma: 1. });
+        assert_ne!(
+            FilterType::Kaiser { beta: 4. },
+            FilterType::Gaussian { sigma: 4. }
+        );
+        assert_eq!(FilterType::Lanczos3, FilterType::Lanczos3);
+        assert_ne!(FilterType::Lanczos3, FilterType::Lanczos2);
+        let custom = FilterType::Custom(Filter::new(|x| (1. - x.abs()).max(0.), 1.));
+        assert_eq!(custom, custom.clone());
+        assert_ne!(
+            custom,
+            FilterType::Custom(Filter::new(|x| (1. - x.abs()).max(0.), 1.))
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use std::arch::aarch64::*;
+
+use crate::convolution::{Coefficients, CoefficientsChunk};
+use crate::pixels::I32;
+use crate::{ImageView, ImageViewMut};
+
+#[inline]
+pub(crate) fn horiz_convolution(
+    src_image: &ImageView<I32>,
+    dst_image: &mut ImageViewMut<I32>,
+    offset: u32,
+    coeffs: Coefficients,
+) {
+    let coefficients_chunks = coeffs.get_chunks();
+    let src_rows = src_image.iter_rows(offset);
+    let dst_rows = dst_image.iter_rows_mut();
+    for (dst_row, src_row) in dst_rows.zip(src_rows) {
+        unsafe {
+            horiz_convolution_row(src_row, dst_row, &coefficients_chunks);
+        }
+    }
+}
+
+#[inline]
+pub(crate) fn vert_convolution(
+    src_image: &ImageView<I32>,
+    dst_image: &mut ImageViewMut<I32>,
+    offset: u32,
+    coeffs: Coefficients,
+) {
+    let coefficients_chunks = coeffs.get_chunks();
+    let dst_rows = dst_image.iter_rows_mut();
+    for (dst_row, coeffs_chunk) in dst_rows.zip(&coefficients_chunks) {
+        unsafe {
+            vert_convolution_into_one_row(src_image, dst_row, offset as usize, coeffs_chunk);
+        }
+    }
+}
+
+/// Loads 4 pixels and converts them into `f64` values.
+///
+/// For safety, it is necessary to ensure that `ptr` points
+/// to at least 4 pixels.
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn load_4_pixels(ptr: *const I32) -> (float64x2_t, float64x2_t) {
+    let pixels = vld1q_s32(ptr as *const i32);
+    (
+        vcvtq_f64_s64(vmovl_s32(vget_low_s32(pixels))),
+        vcvtq_f64_s64(vmovl_high_s32(pixels)),
+    )
+}
+
+/// For safety, it is necessary to ensure the following conditions:
+/// - coefficients_chunks.len() == dst_row.len()
+/// - max(chunk.start + chunk.values.len() for chunk in coefficients_chunks) <= src_row.len()
+#[target_feature(enable = "neon")]
+unsafe fn horiz_convolution_row(
+    src_row: &[I32],
+    dst_row: &mut [I32],
+    coefficients_chunks: &[CoefficientsChunk],
+) {
+    for (dst_pixel, coeffs_chunk) in dst_row.iter_mut().zip(coefficients_chunks) {
+        let first_x_src = coeffs_chunk.start as usize;
+        let src_pixels = src_row.get_unchecked(first_x_src..);
+        let coeffs = coeffs_chunk.values;
+
+        let mut sum0 = vdupq_n_f64(0.);
+        let mut sum1 = vdupq_n_f64(0.);
+        let mut coeffs_chunks = coeffs.chunks_exact(4);
+        for (k, pixels) in (&mut coeffs_chunks).zip(src_pixels.chunks_exact(4)) {
+            let (pixels0, pixels1) = load_4_pixels(pixels.as_ptr());
+            sum0 = vaddq_f64(sum0, vmulq_f64(pixels0, vld1q_f64(k.as_ptr())));
+            sum1 = vaddq_f64(sum1, vmulq_f64(pixels1, vld1q_f64(k.as_ptr().add(2))));
+        }
+        let mut ss = horizontal_sum(sum0, sum1);
+
+        let reminder = coeffs_chunks.remainder();
+        let src_pixels = src_pixels.get_unchecked(coeffs.len() - reminder.len()..);
+        for (&k, &pixel) in reminder.iter().zip(src_pixels) {
+            ss += pixel.0 as f64 * k;
+        }
+        dst_pixel.0 = ss.round() as i32;
+    }
+}
+
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn horizontal_sum(sum0: float64x2_t, sum1: float64x2_t) -> f64 {
+    vaddvq_f64(vaddq_f64(sum0, sum1))
+}
+
+/// For safety, it is necessary to ensure the following conditions:
+/// - src_x + dst_row.len() <= src_image.width()
+/// - coeffs_chunk.start + coeffs_chunk.values.len() <= src_image.height()
+#[target_feature(enable = "neon")]
+unsafe fn vert_convolution_into_one_row(
+    src_image: &ImageView<I32>,
+    dst_row: &mut [I32],
+    mut src_x: usize,
+    coeffs_chunk: &CoefficientsChunk,
+) {
+    let y_start = coeffs_chunk.start;
+    let coeffs = coeffs_chunk.values;
+    let mut buf = [0f64; 4];
+
+    let mut dst_chunks = dst_row.chunks_exact_mut(4);
+    for dst_pixels in &mut dst_chunks {
+        // Every lane accumulates values of its own pixel in the same
+        // order as native implementation does.
+        let mut sum0 = vdupq_n_f64(0.);
+        let mut sum1 = vdupq_n_f64(0.);
+        for (src_row, &k) in src_image.iter_rows(y_start).zip(coeffs) {
+            let k = vdupq_n_f64(k);
+            let (pixels0, pixels1) = load_4_pixels(src_row.get_unchecked(src_x..).as_ptr());
+            sum0 = vaddq_f64(sum0, vmulq_f64(pixels0, k));
+            sum1 = vaddq_f64(sum1, vmulq_f64(pixels1, k));
+        }
+        vst1q_f64(buf.as_mut_ptr(), sum0);
+        vst1q_f64(buf.as_mut_ptr().add(2), sum1);
+        for (dst_pixel, &ss) in dst_pixels.iter_mut().zip(&buf) {
+            dst_pixel.0 = ss.round() as i32;
+        }
+        src_x += 4;
+    }
+
+    for dst_pixel in dst_chunks.into_remainder() {
+        let mut ss = 0.;
+        for (src_row, &k) in src_image.iter_rows(y_start).zip(coeffs) {
+            let src_pixel = src_row.get_unchecked(src_x);
+            ss += src_pixel.0 as f64 * k;
+        }
+        dst_pixel.0 = ss.round() as i32;
+        src_x += 1;
+    }
+}
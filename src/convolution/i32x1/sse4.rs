@@ -0,0 +1,137 @@
+use std::arch::x86_64::*;
+
+use crate::convolution::{Coefficients, CoefficientsChunk};
+use crate::pixels::I32;
+use crate::{ImageView, ImageViewMut};
+
+#[inline]
+pub(crate) fn horiz_convolution(
+    src_image: &ImageView<I32>,
+    dst_image: &mut ImageViewMut<I32>,
+    offset: u32,
+    coeffs: Coefficients,
+) {
+    let coefficients_chunks = coeffs.get_chunks();
+    let src_rows = src_image.iter_rows(offset);
+    let dst_rows = dst_image.iter_rows_mut();
+    for (dst_row, src_row) in dst_rows.zip(src_rows) {
+        unsafe {
+            horiz_convolution_row(src_row, dst_row, &coefficients_chunks);
+        }
+    }
+}
+
+#[inline]
+pub(crate) fn vert_convolution(
+    src_image: &ImageView<I32>,
+    dst_image: &mut ImageViewMut<I32>,
+    offset: u32,
+    coeffs: Coefficients,
+) {
+    let coefficients_chunks = coeffs.get_chunks();
+    let dst_rows = dst_image.iter_rows_mut();
+    for (dst_row, coeffs_chunk) in dst_rows.zip(&coefficients_chunks) {
+        unsafe {
+            vert_convolution_into_one_row(src_image, dst_row, offset as usize, coeffs_chunk);
+        }
+    }
+}
+
+/// Loads 4 pixels and converts them into `f64` values.
+///
+/// For safety, it is necessary to ensure that `ptr` points
+/// to at least 4 pixels.
+#[inline]
+#[target_feature(enable = "sse4.1")]
+unsafe fn load_4_pixels(ptr: *const I32) -> (__m128d, __m128d) {
+    let pixels = _mm_loadu_si128(ptr as *const __m128i);
+    (
+        _mm_cvtepi32_pd(pixels),
+        _mm_cvtepi32_pd(_mm_unpackhi_epi64(pixels, pixels)),
+    )
+}
+
+/// For safety, it is necessary to ensure the following conditions:
+/// - coefficients_chunks.len() == dst_row.len()
+/// - max(chunk.start + chunk.values.len() for chunk in coefficients_chunks) <= src_row.len()
+#[target_feature(enable = "sse4.1")]
+unsafe fn horiz_convolution_row(
+    src_row: &[I32],
+    dst_row: &mut [I32],
+    coefficients_chunks: &[CoefficientsChunk],
+) {
+    for (dst_pixel, coeffs_chunk) in dst_row.iter_mut().zip(coefficients_chunks) {
+        let first_x_src = coeffs_chunk.start as usize;
+        let src_pixels = src_row.get_unchecked(first_x_src..);
+        let coeffs = coeffs_chunk.values;
+
+        let mut sum0 = _mm_setzero_pd();
+        let mut sum1 = _mm_setzero_pd();
+        let mut coeffs_chunks = coeffs.chunks_exact(4);
+        for (k, pixels) in (&mut coeffs_chunks).zip(src_pixels.chunks_exact(4)) {
+            let (pixels0, pixels1) = load_4_pixels(pixels.as_ptr());
+            sum0 = _mm_add_pd(sum0, _mm_mul_pd(pixels0, _mm_loadu_pd(k.as_ptr())));
+            sum1 = _mm_add_pd(sum1, _mm_mul_pd(pixels1, _mm_loadu_pd(k.as_ptr().add(2))));
+        }
+        let mut ss = horizontal_sum(sum0, sum1);
+
+        let reminder = coeffs_chunks.remainder();
+        let src_pixels = src_pixels.get_unchecked(coeffs.len() - reminder.len()..);
+        for (&k, &pixel) in reminder.iter().zip(src_pixels) {
+            ss += pixel.0 as f64 * k;
+        }
+        dst_pixel.0 = ss.round() as i32;
+    }
+}
+
+#[inline]
+#[target_feature(enable = "sse4.1")]
+unsafe fn horizontal_sum(sum0: __m128d, sum1: __m128d) -> f64 {
+    let sum = _mm_add_pd(sum0, sum1);
+    _mm_cvtsd_f64(sum) + _mm_cvtsd_f64(_mm_unpackhi_pd(sum, sum))
+}
+
+/// For safety, it is necessary to ensure the following conditions:
+/// - src_x + dst_row.len() <= src_image.width()
+/// - coeffs_chunk.start + coeffs_chunk.values.len() <= src_image.height()
+#[target_feature(enable = "sse4.1")]
+unsafe fn vert_convolution_into_one_row(
+    src_image: &ImageView<I32>,
+    dst_row: &mut [I32],
+    mut src_x: usize,
+    coeffs_chunk: &CoefficientsChunk,
+) {
+    let y_start = coeffs_chunk.start;
+    let coeffs = coeffs_chunk.values;
+    let mut buf = [0f64; 4];
+
+    let mut dst_chunks = dst_row.chunks_exact_mut(4);
+    for dst_pixels in &mut dst_chunks {
+        // Every lane accumulates values of its own pixel in the same
+        // order as native implementation does.
+        let mut sum0 = _mm_setzero_pd();
+        let mut sum1 = _mm_setzero_pd();
+        for (src_row, &k) in src_image.iter_rows(y_start).zip(coeffs) {
+            let k = _mm_set1_pd(k);
+            let (pixels0, pixels1) = load_4_pixels(src_row.get_unchecked(src_x..).as_ptr());
+            sum0 = _mm_add_pd(sum0, _mm_mul_pd(pixels0, k));
+            sum1 = _mm_add_pd(sum1, _mm_mul_pd(pixels1, k));
+        }
+        _mm_storeu_pd(buf.as_mut_ptr(), sum0);
+        _mm_storeu_pd(buf.as_mut_ptr().add(2), sum1);
+        for (dst_pixel, &ss) in dst_pixels.iter_mut().zip(&buf) {
+            dst_pixel.0 = ss.round() as i32;
+        }
+        src_x += 4;
+    }
+
+    for dst_pixel in dst_chunks.into_remainder() {
+        let mut ss = 0.;
+        for (src_row, &k) in src_image.iter_rows(y_start).zip(coeffs) {
+            let src_pixel = src_row.get_unchecked(src_x);
+            ss += src_pixel.0 as f64 * k;
+        }
+        dst_pixel.0 = ss.round() as i32;
+        src_x += 1;
+    }
+}
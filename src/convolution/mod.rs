@@ -1,17 +1,25 @@
 use std::num::NonZeroU32;
+use std::ops::{AddAssign, Mul, Range};
+use std::sync::{Arc, OnceLock};
 
-pub use filters::{get_filter_func, FilterType};
+pub use filters::{get_filter_func, Filter, FilterType};
+pub use nodata::{NoData, NoDataConvolution};
 
 use crate::pixels::PixelExt;
 use crate::CpuExtensions;
 use crate::{ImageView, ImageViewMut};
+use optimisations::NormalizedValues;
 
 #[macro_use]
 mod macros;
 
 mod f32x1;
+mod f32x2;
+mod f32x3;
+mod f32x4;
 mod filters;
 mod i32x1;
+mod nodata;
 mod optimisations;
 mod u16x1;
 mod u16x2;
@@ -26,7 +34,7 @@ mod vertical_u8;
 
 pub trait Convolution
 where
-    Self: PixelExt,
+    Self: PixelExt + Send + Sync,
 {
     fn horiz_convolution(
         src_image: &ImageView<Self>,
@@ -53,22 +61,246 @@ pub struct Bound {
 
 #[derive(Debug, Clone)]
 pub struct Coefficients {
-    pub values: Vec<f64>,
+    /// Values and bounds are shared between all clones of coefficients,
+    /// so cloning of them is cheap.
+    pub values: Arc<[f64]>,
     pub window_size: usize,
-    pub bounds: Vec<Bound>,
+    pub bounds: Arc<[Bound]>,
+    /// Maximal value of coefficients. It is kept the same for all parts
+    /// sliced from one instance of coefficients, so all of them are
+    /// normalized into integers with the same precision.
+    pub max_weight: f64,
+    /// Values normalized into integers. They are calculated on first use
+    /// and shared between all clones of coefficients.
+    normalized_i16: Arc<OnceLock<NormalizedValues<i16>>>,
+    normalized_i32: Arc<OnceLock<NormalizedValues<i32>>>,
+    /// Values converted into `f32`. They are calculated on first use
+    /// and shared between all clones of coefficients.
+    values_f32: Arc<OnceLock<Vec<f32>>>,
+    /// Type used to accumulate values of pixels with floating-point components.
+    pub float_accumulation: FloatAccumulation,
+}
+
+/// Type used to accumulate values of pixels with floating-point
+/// components during convolution.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FloatAccumulation {
+    /// Faster, but less accurate accumulation.
+    F32,
+    /// Slower, but more accurate accumulation.
+    #[default]
+    F64,
+}
+
+/// Convention of mapping coordinates of destination pixels
+/// into coordinates of source image.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PixelAlignment {
+    /// Borders of source and destination images are aligned and centers
+    /// of pixels are at half-integer coordinates (as in Pillow, PyTorch
+    /// with `align_corners=False` or TensorFlow with `half_pixel_centers=True`).
+    #[default]
+    HalfPixel,
+    /// Centers of corner pixels of source and destination images are
+    /// aligned (`align_corners=True` in PyTorch and TensorFlow).
+    AlignCorners,
+    /// Destination pixel with index `x` is mapped into source pixel with
+    /// index `x * scale` (legacy mode of TensorFlow and `nearest` mode
+    /// of PyTorch).
+    Asymmetric,
+}
+
+impl PixelAlignment {
+    /// Returns the source coordinate of the center of destination pixel.
+    /// Pixel with index `i` covers coordinates from `i` to `i + 1`.
+    #[inline]
+    pub(crate) fn src_center(self, in0: f64, in1: f64, out_size: u32, out_x: u32) -> f64 {
+        match self {
+            Self::HalfPixel => {
+                let scale = (in1 - in0) / out_size as f64;
+                in0 + (out_x as f64 + 0.5) * scale
+            }
+            Self::AlignCorners => {
+                let (start, step) = self.nearest_mapping(in0, in1, out_size);
+                start + step * out_x as f64
+            }
+            Self::Asymmetric => {
+                let (start, step) = self.nearest_mapping(in0, in1, out_size);
+                start + step * out_x as f64 + 0.5
+            }
+        }
+    }
+
+    /// Returns `(start, step)` such that the nearest source pixel for
+    /// destination pixel with index `x` is `floor(start + step * x)`.
+    #[inline]
+    pub(crate) fn nearest_mapping(self, in0: f64, in1: f64, out_size: u32) -> (f64, f64) {
+        let scale = (in1 - in0) / out_size as f64;
+        match self {
+            Self::HalfPixel => (in0 + scale * 0.5, scale),
+            Self::AlignCorners => {
+                let step = if out_size > 1 {
+                    (in1 - in0 - 1.).max(0.) / (out_size - 1) as f64
+                } else {
+                    0.
+                };
+                // Coordinates are rounded to the nearest pixel
+                (in0 + 0.5, step)
+            }
+            Self::Asymmetric => (in0, scale),
+        }
+    }
+}
+
+/// Handling of pixels of filter window which are out of the boundaries
+/// of source image.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum EdgeMode {
+    /// Pixels out of the image are excluded and weights
+    /// of the remaining pixels are renormalized.
+    #[default]
+    Truncate,
+    /// Edge pixels are replicated: `aaa|abcd|ddd`
+    /// (`BORDER_REPLICATE` in OpenCV).
+    Clamp,
+    /// Pixels are mirrored including edge pixels: `cba|abcd|dcb`
+    /// (`BORDER_REFLECT` in OpenCV).
+    Mirror,
+    /// Pixels are taken from the opposite side of the image: `bcd|abcd|abc`
+    /// (`BORDER_WRAP` in OpenCV). It keeps seamless textures seamless.
+    Wrap,
+}
+
+impl EdgeMode {
+    /// Maps index of pixel which may be out of the axis with given size
+    /// into index of pixel inside of it.
+    #[inline]
+    pub(crate) fn map_index(self, x: i64, size: u32) -> u32 {
+        let size = size as i64;
+        let x = match self {
+            Self::Truncate | Self::Clamp => x.clamp(0, size - 1),
+            Self::Mirror => {
+                let x = x.rem_euclid(2 * size);
+                if x < size {
+                    x
+                } else {
+                    2 * size - 1 - x
+                }
+            }
+            Self::Wrap => x.rem_euclid(size),
+        };
+        x as u32
+    }
+}
+
+/// Type of values used to accumulate components of pixels
+/// with floating-point components.
+pub(crate) trait FloatAccumulator: Copy + Default + AddAssign + Mul<Output = Self> {
+    fn from_f32(value: f32) -> Self;
+    fn to_f32(self) -> f32;
+    fn get_chunks(coeffs: &Coefficients) -> Vec<CoefficientsChunk<'_, Self>>;
+}
+
+impl FloatAccumulator for f32 {
+    #[inline(always)]
+    fn from_f32(value: f32) -> Self {
+        value
+    }
+
+    #[inline(always)]
+    fn to_f32(self) -> f32 {
+        self
+    }
+
+    fn get_chunks(coeffs: &Coefficients) -> Vec<CoefficientsChunk<'_, Self>> {
+        coeffs.get_chunks_f32()
+    }
+}
+
+impl FloatAccumulator for f64 {
+    #[inline(always)]
+    fn from_f32(value: f32) -> Self {
+        value as f64
+    }
+
+    #[inline(always)]
+    fn to_f32(self) -> f32 {
+        self as f32
+    }
+
+    fn get_chunks(coeffs: &Coefficients) -> Vec<CoefficientsChunk<'_, Self>> {
+        coeffs.get_chunks()
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
-pub struct CoefficientsChunk<'a> {
+pub struct CoefficientsChunk<'a, T = f64> {
     pub start: u32,
-    pub values: &'a [f64],
+    pub values: &'a [T],
 }
 
 impl Coefficients {
+    pub fn new(values: Vec<f64>, window_size: usize, bounds: Vec<Bound>, max_weight: f64) -> Self {
+        Self {
+            values: values.into(),
+            window_size,
+            bounds: bounds.into(),
+            max_weight,
+            normalized_i16: Default::default(),
+            normalized_i32: Default::default(),
+            values_f32: Default::default(),
+            float_accumulation: FloatAccumulation::default(),
+        }
+    }
+
+    pub(crate) fn normalized_i16(&self) -> &NormalizedValues<i16> {
+        self.normalized_i16
+            .get_or_init(|| optimisations::normalize_i16(self))
+    }
+
+    pub(crate) fn normalized_i32(&self) -> &NormalizedValues<i32> {
+        self.normalized_i32
+            .get_or_init(|| optimisations::normalize_i32(self))
+    }
+
+    fn values_f32(&self) -> &[f32] {
+        self.values_f32
+            .get_or_init(|| self.values.iter().map(|&v| v as f32).collect())
+    }
+
+    /// Calculates in advance the values normalized into integers
+    /// (or converted into `f32`) which are used by convolution
+    /// of pixels of type `P`.
+    pub(crate) fn normalize_for<P: PixelExt>(&self) {
+        match P::count_of_component_values() {
+            256 => {
+                self.normalized_i16();
+            }
+            65536 => {
+                self.normalized_i32();
+            }
+            _ if self.float_accumulation == FloatAccumulation::F32 => {
+                self.values_f32();
+            }
+            _ => (),
+        }
+    }
+
     pub fn get_chunks(&self) -> Vec<CoefficientsChunk> {
-        let mut coeffs = self.values.as_slice();
+        self.split_into_chunks(&self.values)
+    }
+
+    /// Returns chunks of coefficients converted into `f32`.
+    pub fn get_chunks_f32(&self) -> Vec<CoefficientsChunk<'_, f32>> {
+        self.split_into_chunks(self.values_f32())
+    }
+
+    fn split_into_chunks<'a, T>(&self, values: &'a [T]) -> Vec<CoefficientsChunk<'a, T>> {
+        let mut coeffs = values;
         let mut res = Vec::with_capacity(self.bounds.len());
-        for bound in &self.bounds {
+        for bound in self.bounds.iter() {
             let (left, right) = coeffs.split_at(self.window_size);
             coeffs = right;
             let size = bound.size as usize;
@@ -79,8 +311,149 @@ impl Coefficients {
         }
         res
     }
+
+    /// Moves bounds of coefficients by `offset` pixels towards
+    /// the start of source axis.
+    pub(crate) fn shift_bounds(&mut self, offset: u32) {
+        self.bounds = self
+            .bounds
+            .iter()
+            .map(|b| Bound {
+                start: b.start - offset,
+                size: b.size,
+            })
+            .collect();
+    }
+
+    /// Returns coefficients for the given range of destination pixels.
+    pub(crate) fn slice(&self, range: Range<usize>) -> Self {
+        let values_range = range.start * self.window_size..range.end * self.window_size;
+        Self {
+            values: self.values[values_range.clone()].into(),
+            window_size: self.window_size,
+            bounds: self.bounds[range].into(),
+            max_weight: self.max_weight,
+            normalized_i16: slice_normalized(&self.normalized_i16, values_range.clone()),
+            normalized_i32: slice_normalized(&self.normalized_i32, values_range.clone()),
+            values_f32: slice_values(&self.values_f32, values_range),
+            float_accumulation: self.float_accumulation,
+        }
+    }
+}
+
+/// Already normalized values are sliced too.
+fn slice_normalized<T: Copy>(
+    normalized: &OnceLock<NormalizedValues<T>>,
+    values_range: Range<usize>,
+) -> Arc<OnceLock<NormalizedValues<T>>> {
+    let res = OnceLock::new();
+    if let Some(normalized) = normalized.get() {
+        let _ = res.set(normalized.slice(values_range));
+    }
+    Arc::new(res)
+}
+
+fn slice_values<T: Copy>(
+    values: &OnceLock<Vec<T>>,
+    values_range: Range<usize>,
+) -> Arc<OnceLock<Vec<T>>> {
+    let res = OnceLock::new();
+    if let Some(values) = values.get() {
+        let _ = res.set(values[values_range].to_vec());
+    }
+    Arc::new(res)
+}
+
+/// Checks preconditions of [`Convolution::horiz_convolution`].
+///
+/// The trait methods are safe, so violated preconditions
+/// cause a panic regardless of the `checked` feature.
+#[inline(always)]
+pub(crate) fn check_horiz_convolution<P: PixelExt>(
+    src_image: &ImageView<P>,
+    dst_image: &ImageViewMut<P>,
+    offset: u32,
+    coeffs: &Coefficients,
+) {
+    let src_rows_count = src_image.height().get().saturating_sub(offset);
+    if src_rows_count < dst_image.height().get() {
+        panic!(
+            "Source image has {} rows after offset {}, but destination image requires {} rows",
+            src_rows_count,
+            offset,
+            dst_image.height()
+        );
+    }
+    if coeffs.bounds.len() != dst_image.width().get() as usize {
+        panic!(
+            "Count of coefficients chunks ({}) is not equal to width of destination image ({})",
+            coeffs.bounds.len(),
+            dst_image.width()
+        );
+    }
+    check_coefficients(coeffs, src_image.width().get());
 }
 
+/// Checks preconditions of [`Convolution::vert_convolution`].
+#[inline(always)]
+pub(crate) fn check_vert_convolution<P: PixelExt>(
+    src_image: &ImageView<P>,
+    dst_image: &ImageViewMut<P>,
+    offset: u32,
+    coeffs: &Coefficients,
+) {
+    let src_columns_count = src_image.width().get().saturating_sub(offset);
+    if src_columns_count < dst_image.width().get() {
+        panic!(
+            "Source image has {} columns after offset {}, but destination image requires {} columns",
+            src_columns_count,
+            offset,
+            dst_image.width()
+        );
+    }
+    if coeffs.bounds.len() != dst_image.height().get() as usize {
+        panic!(
+            "Count of coefficients chunks ({}) is not equal to height of destination image ({})",
+            coeffs.bounds.len(),
+            dst_image.height()
+        );
+    }
+    check_coefficients(coeffs, src_image.height().get());
+}
+
+/// Checks that all coefficients chunks refer to pixels inside
+/// of source image. The check is performed only with enabled
+/// `checked` feature.
+#[inline(always)]
+fn check_coefficients(coeffs: &Coefficients, src_size: u32) {
+    if !cfg!(feature = "checked") {
+        return;
+    }
+    if coeffs.values.len() < coeffs.bounds.len() * coeffs.window_size {
+        panic!(
+            "Count of coefficients ({}) is smaller than required for {} chunks of size {}",
+            coeffs.values.len(),
+            coeffs.bounds.len(),
+            coeffs.window_size
+        );
+    }
+    for bound in coeffs.bounds.iter() {
+        if bound.size as usize > coeffs.window_size || bound.start + bound.size > src_size {
+            panic!(
+                "Coefficients bound {:?} is out of the source size {} or the window size {}",
+                bound, src_size, coeffs.window_size
+            );
+        }
+    }
+}
+
+/// Calculates coefficients of convolution for one axis of image.
+///
+/// Pixels of filter window which are out of the source axis are handled
+/// according to `edge_mode`. [`EdgeMode::Wrap`] requires the source axis
+/// padded by wrapped pixels, so it is handled the same way
+/// as [`EdgeMode::Truncate`] here.
+#[allow(clippy::too_many_arguments)]
 pub fn precompute_coefficients(
     in_size: NonZeroU32,
     in0: f64, // Left border for cropping
@@ -88,12 +461,15 @@ pub fn precompute_coefficients(
     out_size: NonZeroU32,
     filter: &dyn Fn(f64) -> f64,
     filter_support: f64,
+    blur: f64, // Factor of filter support
+    alignment: PixelAlignment,
+    edge_mode: EdgeMode,
 ) -> Coefficients {
     let in_size = in_size.get();
     let out_size = out_size.get();
 
     let scale = (in1 - in0) / out_size as f64;
-    let filter_scale = scale.max(1.0);
+    let filter_scale = scale.max(1.0) * blur;
 
     // Determine filter radius size (length of resampling filter)
     let filter_radius = filter_support * filter_scale;
@@ -110,29 +486,47 @@ pub fn precompute_coefficients(
     for out_x in 0..out_size {
         // Find the point in the input image corresponding to the centre
         // of the current pixel in the output image.
-        let in_center = in0 + (out_x as f64 + 0.5) * scale;
+        let in_center = alignment.src_center(in0, in1, out_size, out_x);
 
         // x_min and x_max are slice bounds for the input pixels relevant
         // to the output pixel we are calculating. Pixel x is relevant
         // if and only if (x >= x_min) && (x < x_max).
         // Invariant: 0 <= x_min < x_max <= width
-        let x_min = (in_center - filter_radius).floor().max(0.) as u32;
-        let x_max = (in_center + filter_radius).ceil().min(in_size as f64) as u32;
+        let x_first = (in_center - filter_radius).floor();
+        let x_last = (in_center + filter_radius).ceil();
+        let x_min = x_first.max(0.) as u32;
+        let x_max = x_last.min(in_size as f64) as u32;
 
         let cur_index = coeffs.len();
+        coeffs.resize(cur_index + (x_max - x_min) as usize, 0.);
         let mut ww: f64 = 0.0;
 
         // Optimisation for follow for-cycle:
         // (x + 0.5) - in_center => x - (in_center - 0.5) => x - center
         let center = in_center - 0.5;
 
-        for x in x_min..x_max {
+        // Pixels out of the image are folded into pixels inside of it.
+        // For centers of output pixels inside of the image the folded
+        // pixels are always between x_min and x_max.
+        let x_range = match edge_mode {
+            EdgeMode::Clamp | EdgeMode::Mirror => x_first as i64..x_last as i64,
+            EdgeMode::Truncate | EdgeMode::Wrap => x_min as i64..x_max as i64,
+        };
+        for x in x_range {
             let w: f64 = filter((x as f64 - center) * recip_filter_scale);
-            coeffs.push(w);
+            // Non-finite weights returned by custom filters are ignored
+            let w = if w.is_finite() { w } else { 0. };
+            let folded_x = edge_mode.map_index(x, in_size).clamp(x_min, x_max - 1);
+            coeffs[cur_index + (folded_x - x_min) as usize] += w;
             ww += w;
         }
         if ww != 0.0 {
             coeffs[cur_index..].iter_mut().for_each(|w| *w /= ww);
+        } else if x_max > x_min {
+            // Filter with small blur factor may not cover any pixel
+            // with non-zero weight, so the nearest pixel is used.
+            let nearest_x = (in_center.floor() as u32).clamp(x_min, x_max - 1);
+            coeffs[cur_index + (nearest_x - x_min) as usize] = 1.;
         }
         // Remaining values should stay empty if they are used despite x_max.
         coeffs.resize(cur_index + window_size, 0.);
@@ -142,9 +536,30 @@ pub fn precompute_coefficients(
         });
     }
 
-    Coefficients {
-        values: coeffs,
-        window_size,
-        bounds,
-    }
+    let max_weight = coeffs.iter().copied().fold(0.0, f64::max);
+    Coefficients::new(coeffs, window_size, bounds, max_weight)
+}
+
+/// Returns coefficients which select the nearest pixel of source image
+/// for every pixel of destination image. Selected pixels are the same
+/// as in the nearest-neighbor algorithm.
+pub fn nearest_coefficients(
+    in_size: NonZeroU32,
+    in0: f64, // Left border for cropping
+    in1: f64, // Right border for cropping
+    out_size: NonZeroU32,
+    alignment: PixelAlignment,
+) -> Coefficients {
+    let max_x = in_size.get() - 1;
+    let out_size = out_size.get();
+    let (in_start, step) = alignment.nearest_mapping(in0, in1, out_size);
+
+    let coeffs = vec![1.; out_size as usize];
+    let bounds = (0..out_size)
+        .map(|out_x| Bound {
+            start: ((in_start + step * out_x as f64) as u32).min(max_x),
+            size: 1,
+        })
+        .collect();
+    Coefficients::new(coeffs, 1, bounds, 1.)
 }
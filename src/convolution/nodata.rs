@@ -0,0 +1,288 @@
+use crate::pixels::{
+    F32x2, F32x3, F32x4, PixelComponent, PixelExt, U16x2, U16x3, U16x4, U8x2, U8x3, U8x4, F32, I32,
+    U16, U8,
+};
+use crate::{ImageView, ImageViewMut};
+
+use super::{check_horiz_convolution, check_vert_convolution, Coefficients, Convolution};
+
+/// Value of pixel's component which means "no data".
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NoData {
+    /// NaN values of floating-point components.
+    /// Components of integer types are always valid.
+    Nan,
+    /// Components equal to the given value. NaN values of
+    /// floating-point components are excluded too.
+    /// `f32` components are compared with the value converted to `f32`.
+    Value(f64),
+}
+
+/// Convolution that excludes "no data" components from the weighted
+/// sum and renormalizes the coefficients of the remaining components
+/// for every pixel of destination image.
+///
+/// Every component of pixel is processed independently. If some
+/// components in the window of filter are "no data", negative
+/// coefficients are excluded too, so the result always stays within
+/// the range of valid components. The "no data" value is written into
+/// destination image only if no valid component contributes into the result.
+pub trait NoDataConvolution
+where
+    Self: Convolution,
+{
+    fn horiz_convolution_nodata(
+        src_image: &ImageView<Self>,
+        dst_image: &mut ImageViewMut<Self>,
+        offset: u32,
+        coeffs: Coefficients,
+        nodata: NoData,
+    );
+
+    fn vert_convolution_nodata(
+        src_image: &ImageView<Self>,
+        dst_image: &mut ImageViewMut<Self>,
+        offset: u32,
+        coeffs: Coefficients,
+        nodata: NoData,
+    );
+}
+
+macro_rules! nodata_convolution_impl {
+    ($($pixel:ty),+) => {
+        $(
+            impl NoDataConvolution for $pixel {
+                fn horiz_convolution_nodata(
+                    src_image: &ImageView<Self>,
+                    dst_image: &mut ImageViewMut<Self>,
+                    offset: u32,
+                    coeffs: Coefficients,
+                    nodata: NoData,
+                ) {
+                    check_horiz_convolution(src_image, dst_image, offset, &coeffs);
+                    horiz_convolution(src_image, dst_image, offset, coeffs, nodata);
+                }
+
+                fn vert_convolution_nodata(
+                    src_image: &ImageView<Self>,
+                    dst_image: &mut ImageViewMut<Self>,
+                    offset: u32,
+                    coeffs: Coefficients,
+                    nodata: NoData,
+                ) {
+                    check_vert_convolution(src_image, dst_image, offset, &coeffs);
+                    vert_convolution(src_image, dst_image, offset, coeffs, nodata);
+                }
+            }
+        )+
+    };
+}
+
+nodata_convolution_impl!(
+    U8, U8x2, U8x3, U8x4, U16, U16x2, U16x3, U16x4, I32, F32, F32x2, F32x3, F32x4
+);
+
+/// Component of pixel that can be "no data".
+trait NoDataComponent: PixelComponent {
+    fn is_nodata(self, nodata: NoData) -> bool;
+
+    fn to_f64(self) -> f64;
+
+    /// Converts value into the component which is not equal to
+    /// "no data" value.
+    fn from_f64(value: f64, nodata: NoData) -> Self;
+
+    fn nodata(nodata: NoData) -> Self;
+}
+
+macro_rules! int_nodata_component_impl {
+    ($($component:ty),+) => {
+        $(
+            impl NoDataComponent for $component {
+                #[inline(always)]
+                fn is_nodata(self, nodata: NoData) -> bool {
+                    match nodata {
+                        NoData::Nan => false,
+                        NoData::Value(value) => self as f64 == value,
+                    }
+                }
+
+                #[inline(always)]
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+
+                #[inline(always)]
+                fn from_f64(value: f64, nodata: NoData) -> Self {
+                    let res = value.round().clamp(Self::MIN as f64, Self::MAX as f64) as Self;
+                    match nodata {
+                        // Rounded value is moved to the nearest valid one
+                        NoData::Value(nodata) if res as f64 == nodata => {
+                            if (value > nodata && res < Self::MAX) || res == Self::MIN {
+                                res + 1
+                            } else {
+                                res - 1
+                            }
+                        }
+                        _ => res,
+                    }
+                }
+
+                #[inline(always)]
+                fn nodata(nodata: NoData) -> Self {
+                    match nodata {
+                        NoData::Nan => 0,
+                        NoData::Value(value) => value as Self,
+                    }
+                }
+            }
+        )+
+    };
+}
+
+int_nodata_component_impl!(u8, u16, i32);
+
+impl NoDataComponent for f32 {
+    #[inline(always)]
+    fn is_nodata(self, nodata: NoData) -> bool {
+        match nodata {
+            NoData::Nan => self.is_nan(),
+            // Sentinel is compared as it is stored in the image
+            NoData::Value(value) => self.is_nan() || self == value as f32,
+        }
+    }
+
+    #[inline(always)]
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    #[inline(always)]
+    fn from_f64(value: f64, nodata: NoData) -> Self {
+        let res = value as f32;
+        match nodata {
+            // Value is moved to the nearest valid one
+            NoData::Value(nodata) if res == nodata as f32 => {
+                if value > res as f64 {
+                    res.next_up()
+                } else {
+                    res.next_down()
+                }
+            }
+            _ => res,
+        }
+    }
+
+    #[inline(always)]
+    fn nodata(nodata: NoData) -> Self {
+        match nodata {
+            NoData::Nan => f32::NAN,
+            NoData::Value(value) => value as f32,
+        }
+    }
+}
+
+/// Accumulates weighted sums of valid components and their weights.
+#[derive(Default)]
+struct Accumulator {
+    sum: f64,
+    weight: f64,
+    /// Sum and weight of components with positive coefficients.
+    positive_sum: f64,
+    positive_weight: f64,
+    has_nodata: bool,
+}
+
+impl Accumulator {
+    #[inline(always)]
+    fn add<C: NoDataComponent>(&mut self, component: C, k: f64, nodata: NoData) {
+        if component.is_nodata(nodata) {
+            self.has_nodata = true;
+            return;
+        }
+        let value = component.to_f64() * k;
+        self.sum += value;
+        self.weight += k;
+        if k > 0. {
+            self.positive_sum += value;
+            self.positive_weight += k;
+        }
+    }
+
+    #[inline(always)]
+    fn result<C: NoDataComponent>(&self, nodata: NoData) -> C {
+        // Renormalized negative lobes of filter can push the result far
+        // outside the range of valid components if some of them are missing.
+        let (sum, weight) = if self.has_nodata {
+            (self.positive_sum, self.positive_weight)
+        } else {
+            (self.sum, self.weight)
+        };
+        if weight == 0. {
+            C::nodata(nodata)
+        } else {
+            C::from_f64(sum / weight, nodata)
+        }
+    }
+}
+
+fn horiz_convolution<P>(
+    src_image: &ImageView<P>,
+    dst_image: &mut ImageViewMut<P>,
+    offset: u32,
+    coeffs: Coefficients,
+    nodata: NoData,
+) where
+    P: PixelExt,
+    P::Component: NoDataComponent,
+{
+    let components_count = P::count_of_components();
+    let coefficients_chunks = coeffs.get_chunks();
+    let src_rows = src_image.iter_rows(offset);
+    let dst_rows = dst_image.iter_rows_mut();
+    for (dst_row, src_row) in dst_rows.zip(src_rows) {
+        let src_components = P::components(src_row);
+        let dst_components = P::components_mut(dst_row);
+        let dst_pixels = dst_components.chunks_exact_mut(components_count);
+        for (dst_pixel, coeffs_chunk) in dst_pixels.zip(&coefficients_chunks) {
+            let first_x_src = coeffs_chunk.start as usize;
+            for (c, dst_component) in dst_pixel.iter_mut().enumerate() {
+                let mut acc = Accumulator::default();
+                let src_pixels =
+                    src_components[first_x_src * components_count..].chunks_exact(components_count);
+                for (&k, src_pixel) in coeffs_chunk.values.iter().zip(src_pixels) {
+                    acc.add(src_pixel[c], k, nodata);
+                }
+                *dst_component = acc.result(nodata);
+            }
+        }
+    }
+}
+
+fn vert_convolution<P>(
+    src_image: &ImageView<P>,
+    dst_image: &mut ImageViewMut<P>,
+    offset: u32,
+    coeffs: Coefficients,
+    nodata: NoData,
+) where
+    P: PixelExt,
+    P::Component: NoDataComponent,
+{
+    let components_count = P::count_of_components();
+    let coefficients_chunks = coeffs.get_chunks();
+    let dst_rows = dst_image.iter_rows_mut();
+    let start_src_index = offset as usize * components_count;
+    for (coeffs_chunk, dst_row) in coefficients_chunks.iter().zip(dst_rows) {
+        let first_y_src = coeffs_chunk.start;
+        let dst_components = P::components_mut(dst_row);
+        for (src_index, dst_component) in (start_src_index..).zip(dst_components.iter_mut()) {
+            let mut acc = Accumulator::default();
+            let src_rows = src_image.iter_rows(first_y_src);
+            for (src_row, &k) in src_rows.zip(coeffs_chunk.values) {
+                acc.add(P::components(src_row)[src_index], k, nodata);
+            }
+            *dst_component = acc.result(nodata);
+        }
+    }
+}
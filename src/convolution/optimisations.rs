@@ -1,4 +1,5 @@
-use super::Bound;
+use std::ops::Range;
+
 use crate::convolution::Coefficients;
 
 // This code is based on C-implementation from Pillow-SIMD package for Python
@@ -29,12 +30,56 @@ const PRECISION_BITS: u8 = 32 - 8 - 2;
 // We use i16 type to store coefficients.
 const MAX_COEFFS_PRECISION: u8 = 16 - 1;
 
+/// Coefficients normalized into integers with given precision.
+#[derive(Debug, Clone)]
+pub(crate) struct NormalizedValues<T> {
+    pub values: Vec<T>,
+    pub precision: u8,
+}
+
+impl<T: Copy> NormalizedValues<T> {
+    /// Returns normalized values for the given range of destination pixels.
+    pub fn slice(&self, values_range: Range<usize>) -> Self {
+        Self {
+            values: self.values[values_range].to_vec(),
+            precision: self.precision,
+        }
+    }
+}
+
+pub(crate) fn normalize_i16(coefficients: &Coefficients) -> NormalizedValues<i16> {
+    let max_weight = coefficients.max_weight;
+
+    let mut precision = 0u8;
+    for cur_precision in 0..PRECISION_BITS {
+        precision = cur_precision;
+        let next_value: i32 = (max_weight * (1 << (precision + 1)) as f64).round() as i32;
+        // The next value will be outside the range, so just stop
+        if next_value >= (1 << MAX_COEFFS_PRECISION) {
+            break;
+        }
+    }
+    debug_assert!(precision >= 4); // required for some SIMD optimisations
+
+    let mut values_i16 = Vec::with_capacity(coefficients.values.len());
+
+    let scale = (1 << precision) as f64;
+    for src in coefficients.values.iter().copied() {
+        values_i16.push((src * scale).round() as i16);
+    }
+    NormalizedValues {
+        values: values_i16,
+        precision,
+    }
+}
+
 /// Converts `Vec<f64>` into `Vec<i16>`.
+///
+/// Normalized values are stored inside of coefficients,
+/// so they are calculated only once for the same coefficients.
 pub(crate) struct Normalizer16 {
-    values: Vec<i16>,
+    coefficients: Coefficients,
     precision: u8,
-    window_size: usize,
-    bounds: Vec<Bound>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -46,44 +91,20 @@ pub(crate) struct CoefficientsI16Chunk<'a> {
 impl Normalizer16 {
     #[inline]
     pub fn new(coefficients: Coefficients) -> Self {
-        let max_weight = coefficients
-            .values
-            .iter()
-            .max_by(|&x, &y| x.partial_cmp(y).unwrap())
-            .unwrap_or(&0.0)
-            .to_owned();
-
-        let mut precision = 0u8;
-        for cur_precision in 0..PRECISION_BITS {
-            precision = cur_precision;
-            let next_value: i32 = (max_weight * (1 << (precision + 1)) as f64).round() as i32;
-            // The next value will be outside the range, so just stop
-            if next_value >= (1 << MAX_COEFFS_PRECISION) {
-                break;
-            }
-        }
-        debug_assert!(precision >= 4); // required for some SIMD optimisations
-
-        let mut values_i16 = Vec::with_capacity(coefficients.values.len());
-
-        let scale = (1 << precision) as f64;
-        for src in coefficients.values.iter().copied() {
-            values_i16.push((src * scale).round() as i16);
-        }
+        let precision = coefficients.normalized_i16().precision;
         Self {
-            values: values_i16,
+            coefficients,
             precision,
-            window_size: coefficients.window_size,
-            bounds: coefficients.bounds,
         }
     }
 
     #[inline]
     pub fn normalized_chunks(&self) -> Vec<CoefficientsI16Chunk> {
-        let mut cooefs = self.values.as_slice();
-        let mut res = Vec::with_capacity(self.bounds.len());
-        for bound in self.bounds.iter() {
-            let (left, right) = cooefs.split_at(self.window_size);
+        let window_size = self.coefficients.window_size;
+        let mut cooefs = self.coefficients.normalized_i16().values.as_slice();
+        let mut res = Vec::with_capacity(self.coefficients.bounds.len());
+        for bound in self.coefficients.bounds.iter() {
+            let (left, right) = cooefs.split_at(window_size);
             cooefs = right;
             let size = bound.size as usize;
             res.push(CoefficientsI16Chunk {
@@ -126,55 +147,58 @@ pub(crate) struct CoefficientsI32Chunk<'a> {
     pub values: &'a [i32],
 }
 
+pub(crate) fn normalize_i32(coefficients: &Coefficients) -> NormalizedValues<i32> {
+    let max_weight = coefficients.max_weight;
+
+    let mut precision = 0u8;
+    for cur_precision in 0..PRECISION16_BITS {
+        precision = cur_precision;
+        let next_value: i64 = (max_weight * (1i64 << (precision + 1)) as f64).round() as i64;
+        // The next value will be outside the range, so just stop
+        if next_value >= (1i64 << MAX_COEFFS_PRECISION16) {
+            break;
+        }
+    }
+    debug_assert!(precision >= 4); // required for some SIMD optimisations
+
+    let mut values_i32 = Vec::with_capacity(coefficients.values.len());
+
+    let scale = (1i64 << precision) as f64;
+    for src in coefficients.values.iter().copied() {
+        values_i32.push((src * scale).round() as i32);
+    }
+    NormalizedValues {
+        values: values_i32,
+        precision,
+    }
+}
+
 /// Converts `Vec<f64>` into `Vec<i32>`.
+///
+/// Normalized values are stored inside of coefficients,
+/// so they are calculated only once for the same coefficients.
 pub(crate) struct Normalizer32 {
-    values: Vec<i32>,
+    coefficients: Coefficients,
     precision: u8,
-    window_size: usize,
-    bounds: Vec<Bound>,
 }
 
 impl Normalizer32 {
     #[inline]
     pub fn new(coefficients: Coefficients) -> Self {
-        let max_weight = coefficients
-            .values
-            .iter()
-            .max_by(|&x, &y| x.partial_cmp(y).unwrap())
-            .unwrap_or(&0.0)
-            .to_owned();
-
-        let mut precision = 0u8;
-        for cur_precision in 0..PRECISION16_BITS {
-            precision = cur_precision;
-            let next_value: i64 = (max_weight * (1i64 << (precision + 1)) as f64).round() as i64;
-            // The next value will be outside the range, so just stop
-            if next_value >= (1i64 << MAX_COEFFS_PRECISION16) {
-                break;
-            }
-        }
-        debug_assert!(precision >= 4); // required for some SIMD optimisations
-
-        let mut values_i32 = Vec::with_capacity(coefficients.values.len());
-
-        let scale = (1i64 << precision) as f64;
-        for src in coefficients.values.iter().copied() {
-            values_i32.push((src * scale).round() as i32);
-        }
+        let precision = coefficients.normalized_i32().precision;
         Self {
-            values: values_i32,
+            coefficients,
             precision,
-            window_size: coefficients.window_size,
-            bounds: coefficients.bounds,
         }
     }
 
     #[inline]
     pub fn normalized_chunks(&self) -> Vec<CoefficientsI32Chunk> {
-        let mut cooefs = self.values.as_slice();
-        let mut res = Vec::with_capacity(self.bounds.len());
-        for bound in self.bounds.iter() {
-            let (left, right) = cooefs.split_at(self.window_size);
+        let window_size = self.coefficients.window_size;
+        let mut cooefs = self.coefficients.normalized_i32().values.as_slice();
+        let mut res = Vec::with_capacity(self.coefficients.bounds.len());
+        for bound in self.coefficients.bounds.iter() {
+            let (left, right) = cooefs.split_at(window_size);
             cooefs = right;
             let size = bound.size as usize;
             res.push(CoefficientsI32Chunk {
@@ -201,11 +225,7 @@ mod tests {
     use super::*;
 
     fn get_coefficients(value: f64) -> Coefficients {
-        Coefficients {
-            values: vec![value],
-            window_size: 0,
-            bounds: vec![],
-        }
+        Coefficients::new(vec![value], 0, vec![], value)
     }
 
     #[test]
@@ -46,7 +46,7 @@ pub(crate) fn horiz_convolution(
 #[target_feature(enable = "sse4.1")]
 unsafe fn horiz_convolution_four_rows(
     src_rows: [&[U16]; 4],
-    dst_rows: [&mut &mut [U16]; 4],
+    dst_rows: [&mut [U16]; 4],
     coefficients_chunks: &[optimisations::CoefficientsI32Chunk],
     normalizer: &optimisations::Normalizer32,
 ) {
@@ -48,7 +48,7 @@ pub(crate) fn horiz_convolution(
 #[target_feature(enable = "neon")]
 unsafe fn horiz_convolution_four_rows(
     src_rows: [&[U16x2]; 4],
-    dst_rows: [&mut &mut [U16x2]; 4],
+    dst_rows: [&mut [U16x2]; 4],
     coefficients_chunks: &[optimisations::CoefficientsI32Chunk],
     precision: u8,
 ) {
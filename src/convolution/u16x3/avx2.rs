@@ -46,7 +46,7 @@ pub(crate) fn horiz_convolution(
 #[target_feature(enable = "avx2")]
 unsafe fn horiz_convolution_four_rows(
     src_rows: [&[U16x3]; 4],
-    dst_rows: [&mut &mut [U16x3]; 4],
+    dst_rows: [&mut [U16x3]; 4],
     coefficients_chunks: &[optimisations::CoefficientsI32Chunk],
     normalizer: &optimisations::Normalizer32,
 ) {
@@ -48,7 +48,7 @@ pub(crate) fn horiz_convolution(
 #[target_feature(enable = "simd128")]
 unsafe fn horiz_convolution_8u4x(
     src_rows: [&[U16x3]; 4],
-    dst_rows: [&mut &mut [U16x3]; 4],
+    dst_rows: [&mut [U16x3]; 4],
     coefficients_chunks: &[CoefficientsI32Chunk],
     normalizer: &optimisations::Normalizer32,
 ) {
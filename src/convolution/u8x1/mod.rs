@@ -3,7 +3,7 @@ use crate::pixels::U8;
 use crate::CpuExtensions;
 use crate::{ImageView, ImageViewMut};
 
-use super::{Coefficients, Convolution};
+use super::{check_horiz_convolution, check_vert_convolution, Coefficients, Convolution};
 
 #[cfg(target_arch = "x86_64")]
 mod avx2;
@@ -23,6 +23,7 @@ impl Convolution for U8 {
         coeffs: Coefficients,
         cpu_extensions: CpuExtensions,
     ) {
+        check_horiz_convolution(src_image, dst_image, offset, &coeffs);
         match cpu_extensions {
             #[cfg(target_arch = "x86_64")]
             CpuExtensions::Avx2 => avx2::horiz_convolution(src_image, dst_image, offset, coeffs),
@@ -45,6 +46,7 @@ impl Convolution for U8 {
         coeffs: Coefficients,
         cpu_extensions: CpuExtensions,
     ) {
+        check_vert_convolution(src_image, dst_image, offset, &coeffs);
         vert_convolution_u8(src_image, dst_image, offset, coeffs, cpu_extensions);
     }
 }
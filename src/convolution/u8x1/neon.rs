@@ -47,7 +47,7 @@ pub(crate) fn horiz_convolution(
 #[target_feature(enable = "neon")]
 unsafe fn horiz_convolution_four_rows(
     src_rows: [&[U8]; 4],
-    dst_rows: [&mut &mut [U8]; 4],
+    dst_rows: [&mut [U8]; 4],
     coefficients_chunks: &[optimisations::CoefficientsI16Chunk],
     normalizer: &optimisations::Normalizer16,
 ) {
@@ -48,7 +48,7 @@ pub(crate) fn horiz_convolution(
 #[target_feature(enable = "neon")]
 unsafe fn horiz_convolution_four_rows(
     src_rows: [&[U8x2]; 4],
-    dst_rows: [&mut &mut [U8x2]; 4],
+    dst_rows: [&mut [U8x2]; 4],
     coefficients_chunks: &[optimisations::CoefficientsI16Chunk],
     precision: u8,
 ) {
@@ -48,7 +48,7 @@ pub(crate) fn horiz_convolution(
 #[target_feature(enable = "sse4.1")]
 unsafe fn horiz_convolution_four_rows(
     src_rows: [&[U8x2]; 4],
-    dst_rows: [&mut &mut [U8x2]; 4],
+    dst_rows: [&mut [U8x2]; 4],
     coefficients_chunks: &[optimisations::CoefficientsI16Chunk],
     normalizer: &optimisations::Normalizer16,
 ) {
@@ -148,7 +148,7 @@ unsafe fn horiz_convolution_four_rows(
 #[target_feature(enable = "sse4.1")]
 unsafe fn set_dst_pixel(
     raw: __m128i,
-    d_row: &mut &mut [U8x2],
+    d_row: &mut [U8x2],
     dst_x: usize,
     normalizer: &optimisations::Normalizer16,
 ) {
@@ -50,7 +50,7 @@ pub(crate) fn horiz_convolution(
 #[target_feature(enable = "avx2")]
 unsafe fn horiz_convolution_8u4x(
     src_rows: [&[U8x3]; 4],
-    dst_rows: [&mut &mut [U8x3]; 4],
+    dst_rows: [&mut [U8x3]; 4],
     coefficients_chunks: &[optimisations::CoefficientsI16Chunk],
     precision: u8,
 ) {
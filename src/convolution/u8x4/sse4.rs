@@ -52,7 +52,7 @@ pub(crate) fn horiz_convolution(
 #[target_feature(enable = "sse4.1")]
 unsafe fn horiz_convolution_8u4x(
     src_rows: [&[U8x4]; 4],
-    dst_rows: [&mut &mut [U8x4]; 4],
+    dst_rows: [&mut [U8x4]; 4],
     coefficients_chunks: &[optimisations::CoefficientsI16Chunk],
     precision: u8,
 ) {
@@ -0,0 +1,292 @@
+use std::num::NonZeroU32;
+
+use crate::pixels::*;
+use crate::{DynamicImageView, DynamicImageViewMut, Error, Image};
+
+/// Simple container of image data with type of pixels known only at runtime.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum DynamicImage<'a> {
+    U8(Image<'a, U8>),
+    U8x2(Image<'a, U8x2>),
+    U8x3(Image<'a, U8x3>),
+    U8x4(Image<'a, U8x4>),
+    U16(Image<'a, U16>),
+    U16x2(Image<'a, U16x2>),
+    U16x3(Image<'a, U16x3>),
+    U16x4(Image<'a, U16x4>),
+    I32(Image<'a, I32>),
+    F32(Image<'a, F32>),
+    F32x2(Image<'a, F32x2>),
+    F32x3(Image<'a, F32x3>),
+    F32x4(Image<'a, F32x4>),
+}
+
+macro_rules! from_typed_image {
+    ($($variant:ident),+) => {
+        $(
+            impl<'a> From<Image<'a, $variant>> for DynamicImage<'a> {
+                fn from(image: Image<'a, $variant>) -> Self {
+                    Self::$variant(image)
+                }
+            }
+        )+
+    };
+}
+
+from_typed_image!(U8, U8x2, U8x3, U8x4, U16, U16x2, U16x3, U16x4, I32, F32, F32x2, F32x3, F32x4);
+
+impl<'a> DynamicImage<'a> {
+    /// Create empty image with given dimensions and pixel type.
+    pub fn new(width: NonZeroU32, height: NonZeroU32, pixel_type: PixelType) -> Self {
+        match pixel_type {
+            PixelType::U8 => Image::<U8>::new(width, height).into(),
+            PixelType::U8x2 => Image::<U8x2>::new(width, height).into(),
+            PixelType::U8x3 => Image::<U8x3>::new(width, height).into(),
+            PixelType::U8x4 => Image::<U8x4>::new(width, height).into(),
+            PixelType::U16 => Image::<U16>::new(width, height).into(),
+            PixelType::U16x2 => Image::<U16x2>::new(width, height).into(),
+            PixelType::U16x3 => Image::<U16x3>::new(width, height).into(),
+            PixelType::U16x4 => Image::<U16x4>::new(width, height).into(),
+            PixelType::I32 => Image::<I32>::new(width, height).into(),
+            PixelType::F32 => Image::<F32>::new(width, height).into(),
+            PixelType::F32x2 => Image::<F32x2>::new(width, height).into(),
+            PixelType::F32x3 => Image::<F32x3>::new(width, height).into(),
+            PixelType::F32x4 => Image::<F32x4>::new(width, height).into(),
+        }
+    }
+
+    /// Create image from buffer with pixels of given type.
+    pub fn try_from_vec_u8(
+        width: NonZeroU32,
+        height: NonZeroU32,
+        buffer: Vec<u8>,
+        pixel_type: PixelType,
+    ) -> Result<Self, Error> {
+        Ok(match pixel_type {
+            PixelType::U8 => Image::<U8>::try_from_vec_u8(width, height, buffer)?.into(),
+            PixelType::U8x2 => Image::<U8x2>::try_from_vec_u8(width, height, buffer)?.into(),
+            PixelType::U8x3 => Image::<U8x3>::try_from_vec_u8(width, height, buffer)?.into(),
+            PixelType::U8x4 => Image::<U8x4>::try_from_vec_u8(width, height, buffer)?.into(),
+            PixelType::U16 => Image::<U16>::try_from_vec_u8(width, height, buffer)?.into(),
+            PixelType::U16x2 => Image::<U16x2>::try_from_vec_u8(width, height, buffer)?.into(),
+            PixelType::U16x3 => Image::<U16x3>::try_from_vec_u8(width, height, buffer)?.into(),
+            PixelType::U16x4 => Image::<U16x4>::try_from_vec_u8(width, height, buffer)?.into(),
+            PixelType::I32 => Image::<I32>::try_from_vec_u8(width, height, buffer)?.into(),
+            PixelType::F32 => Image::<F32>::try_from_vec_u8(width, height, buffer)?.into(),
+            PixelType::F32x2 => Image::<F32x2>::try_from_vec_u8(width, height, buffer)?.into(),
+            PixelType::F32x3 => Image::<F32x3>::try_from_vec_u8(width, height, buffer)?.into(),
+            PixelType::F32x4 => Image::<F32x4>::try_from_vec_u8(width, height, buffer)?.into(),
+        })
+    }
+
+    /// Create image from mutable slice with pixels of given type.
+    pub fn try_from_slice_u8(
+        width: NonZeroU32,
+        height: NonZeroU32,
+        buffer: &'a mut [u8],
+        pixel_type: PixelType,
+    ) -> Result<Self, Error> {
+        Ok(match pixel_type {
+            PixelType::U8 => Image::<U8>::try_from_slice_u8(width, height, buffer)?.into(),
+            PixelType::U8x2 => Image::<U8x2>::try_from_slice_u8(width, height, buffer)?.into(),
+            PixelType::U8x3 => Image::<U8x3>::try_from_slice_u8(width, height, buffer)?.into(),
+            PixelType::U8x4 => Image::<U8x4>::try_from_slice_u8(width, height, buffer)?.into(),
+            PixelType::U16 => Image::<U16>::try_from_slice_u8(width, height, buffer)?.into(),
+            PixelType::U16x2 => Image::<U16x2>::try_from_slice_u8(width, height, buffer)?.into(),
+            PixelType::U16x3 => Image::<U16x3>::try_from_slice_u8(width, height, buffer)?.into(),
+            PixelType::U16x4 => Image::<U16x4>::try_from_slice_u8(width, height, buffer)?.into(),
+            PixelType::I32 => Image::<I32>::try_from_slice_u8(width, height, buffer)?.into(),
+            PixelType::F32 => Image::<F32>::try_from_slice_u8(width, height, buffer)?.into(),
+            PixelType::F32x2 => Image::<F32x2>::try_from_slice_u8(width, height, buffer)?.into(),
+            PixelType::F32x3 => Image::<F32x3>::try_from_slice_u8(width, height, buffer)?.into(),
+            PixelType::F32x4 => Image::<F32x4>::try_from_slice_u8(width, height, buffer)?.into(),
+        })
+    }
+
+    /// Create image from buffer with pixels of given type and rows
+    /// which start every `stride` bytes.
+    pub fn try_from_vec_u8_with_stride(
+        width: NonZeroU32,
+        height: NonZeroU32,
+        stride: usize,
+        buffer: Vec<u8>,
+        pixel_type: PixelType,
+    ) -> Result<Self, Error> {
+        Ok(match pixel_type {
+            PixelType::U8 => {
+                Image::<U8>::try_from_vec_u8_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::U8x2 => {
+                Image::<U8x2>::try_from_vec_u8_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::U8x3 => {
+                Image::<U8x3>::try_from_vec_u8_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::U8x4 => {
+                Image::<U8x4>::try_from_vec_u8_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::U16 => {
+                Image::<U16>::try_from_vec_u8_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::U16x2 => {
+                Image::<U16x2>::try_from_vec_u8_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::U16x3 => {
+                Image::<U16x3>::try_from_vec_u8_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::U16x4 => {
+                Image::<U16x4>::try_from_vec_u8_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::I32 => {
+                Image::<I32>::try_from_vec_u8_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::F32 => {
+                Image::<F32>::try_from_vec_u8_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::F32x2 => {
+                Image::<F32x2>::try_from_vec_u8_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::F32x3 => {
+                Image::<F32x3>::try_from_vec_u8_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::F32x4 => {
+                Image::<F32x4>::try_from_vec_u8_with_stride(width, height, stride, buffer)?.into()
+            }
+        })
+    }
+
+    /// Create image from mutable slice with pixels of given type
+    /// and rows which start every `stride` bytes.
+    pub fn try_from_slice_u8_with_stride(
+        width: NonZeroU32,
+        height: NonZeroU32,
+        stride: usize,
+        buffer: &'a mut [u8],
+        pixel_type: PixelType,
+    ) -> Result<Self, Error> {
+        Ok(match pixel_type {
+            PixelType::U8 => {
+                Image::<U8>::try_from_slice_u8_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::U8x2 => {
+                Image::<U8x2>::try_from_slice_u8_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::U8x3 => {
+                Image::<U8x3>::try_from_slice_u8_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::U8x4 => {
+                Image::<U8x4>::try_from_slice_u8_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::U16 => {
+                Image::<U16>::try_from_slice_u8_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::U16x2 => {
+                Image::<U16x2>::try_from_slice_u8_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::U16x3 => {
+                Image::<U16x3>::try_from_slice_u8_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::U16x4 => {
+                Image::<U16x4>::try_from_slice_u8_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::I32 => {
+                Image::<I32>::try_from_slice_u8_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::F32 => {
+                Image::<F32>::try_from_slice_u8_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::F32x2 => {
+                Image::<F32x2>::try_from_slice_u8_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::F32x3 => {
+                Image::<F32x3>::try_from_slice_u8_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::F32x4 => {
+                Image::<F32x4>::try_from_slice_u8_with_stride(width, height, stride, buffer)?.into()
+            }
+        })
+    }
+
+    /// Creates a copy of the image.
+    pub fn copy(&self) -> DynamicImage<'static> {
+        dynamic_map!(Self, self, |image| image.copy().into())
+    }
+
+    pub fn pixel_type(&self) -> PixelType {
+        match self {
+            Self::U8(_) => PixelType::U8,
+            Self::U8x2(_) => PixelType::U8x2,
+            Self::U8x3(_) => PixelType::U8x3,
+            Self::U8x4(_) => PixelType::U8x4,
+            Self::U16(_) => PixelType::U16,
+            Self::U16x2(_) => PixelType::U16x2,
+            Self::U16x3(_) => PixelType::U16x3,
+            Self::U16x4(_) => PixelType::U16x4,
+            Self::I32(_) => PixelType::I32,
+            Self::F32(_) => PixelType::F32,
+            Self::F32x2(_) => PixelType::F32x2,
+            Self::F32x3(_) => PixelType::F32x3,
+            Self::F32x4(_) => PixelType::F32x4,
+        }
+    }
+
+    #[inline(always)]
+    pub fn width(&self) -> NonZeroU32 {
+        dynamic_map!(Self, self, |image| image.width())
+    }
+
+    #[inline(always)]
+    pub fn height(&self) -> NonZeroU32 {
+        dynamic_map!(Self, self, |image| image.height())
+    }
+
+    /// Distance in bytes between starts of adjacent rows of the image.
+    #[inline(always)]
+    pub fn stride(&self) -> usize {
+        dynamic_map!(Self, self, |image| image.stride())
+    }
+
+    /// Buffer with image pixels.
+    #[inline(always)]
+    pub fn buffer(&self) -> &[u8] {
+        dynamic_map!(Self, self, |image| image.buffer())
+    }
+
+    /// Mutable buffer with image pixels.
+    #[inline(always)]
+    pub fn buffer_mut(&mut self) -> &mut [u8] {
+        dynamic_map!(Self, self, |image| image.buffer_mut())
+    }
+
+    #[inline(always)]
+    pub fn into_vec(self) -> Vec<u8> {
+        dynamic_map!(Self, self, |image| image.into_vec())
+    }
+
+    /// # Safety
+    /// The buffer of image must be aligned by the alignment of its pixels.
+    #[inline(always)]
+    pub unsafe fn view(&self) -> DynamicImageView<'_> {
+        dynamic_map!(Self, self, |image| image.view().into())
+    }
+
+    /// # Safety
+    /// The buffer of image must be aligned by the alignment of its pixels.
+    #[inline(always)]
+    pub unsafe fn view_mut(&mut self) -> DynamicImageViewMut<'_> {
+        dynamic_map!(Self, self, |image| image.view_mut().into())
+    }
+
+    /// Checked version of [`DynamicImage::view`].
+    #[inline(always)]
+    pub fn try_view(&self) -> Result<DynamicImageView<'_>, Error> {
+        dynamic_map!(Self, self, |image| Ok(image.try_view()?.into()))
+    }
+
+    /// Checked version of [`DynamicImage::view_mut`].
+    #[inline(always)]
+    pub fn try_view_mut(&mut self) -> Result<DynamicImageViewMut<'_>, Error> {
+        dynamic_map!(Self, self, |image| Ok(image.try_view_mut()?.into()))
+    }
+}
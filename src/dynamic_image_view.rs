@@ -0,0 +1,347 @@
+use std::num::NonZeroU32;
+
+use crate::pixels::*;
+use crate::{CropBox, Error, ImageView, ImageViewMut};
+
+/// Immutable view of image with type of pixels known only at runtime.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum DynamicImageView<'a> {
+    U8(ImageView<'a, U8>),
+    U8x2(ImageView<'a, U8x2>),
+    U8x3(ImageView<'a, U8x3>),
+    U8x4(ImageView<'a, U8x4>),
+    U16(ImageView<'a, U16>),
+    U16x2(ImageView<'a, U16x2>),
+    U16x3(ImageView<'a, U16x3>),
+    U16x4(ImageView<'a, U16x4>),
+    I32(ImageView<'a, I32>),
+    F32(ImageView<'a, F32>),
+    F32x2(ImageView<'a, F32x2>),
+    F32x3(ImageView<'a, F32x3>),
+    F32x4(ImageView<'a, F32x4>),
+}
+
+/// Mutable view of image with type of pixels known only at runtime.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum DynamicImageViewMut<'a> {
+    U8(ImageViewMut<'a, U8>),
+    U8x2(ImageViewMut<'a, U8x2>),
+    U8x3(ImageViewMut<'a, U8x3>),
+    U8x4(ImageViewMut<'a, U8x4>),
+    U16(ImageViewMut<'a, U16>),
+    U16x2(ImageViewMut<'a, U16x2>),
+    U16x3(ImageViewMut<'a, U16x3>),
+    U16x4(ImageViewMut<'a, U16x4>),
+    I32(ImageViewMut<'a, I32>),
+    F32(ImageViewMut<'a, F32>),
+    F32x2(ImageViewMut<'a, F32x2>),
+    F32x3(ImageViewMut<'a, F32x3>),
+    F32x4(ImageViewMut<'a, F32x4>),
+}
+
+macro_rules! from_typed_view {
+    ($($variant:ident),+) => {
+        $(
+            impl<'a> From<ImageView<'a, $variant>> for DynamicImageView<'a> {
+                fn from(view: ImageView<'a, $variant>) -> Self {
+                    Self::$variant(view)
+                }
+            }
+
+            impl<'a> From<ImageViewMut<'a, $variant>> for DynamicImageViewMut<'a> {
+                fn from(view: ImageViewMut<'a, $variant>) -> Self {
+                    Self::$variant(view)
+                }
+            }
+        )+
+    };
+}
+
+from_typed_view!(U8, U8x2, U8x3, U8x4, U16, U16x2, U16x3, U16x4, I32, F32, F32x2, F32x3, F32x4);
+
+impl<'a> DynamicImageView<'a> {
+    /// Creates view of buffer with pixels of given type.
+    pub fn try_new(
+        width: NonZeroU32,
+        height: NonZeroU32,
+        buffer: &'a [u8],
+        pixel_type: PixelType,
+    ) -> Result<Self, Error> {
+        Ok(match pixel_type {
+            PixelType::U8 => ImageView::<U8>::try_new(width, height, buffer)?.into(),
+            PixelType::U8x2 => ImageView::<U8x2>::try_new(width, height, buffer)?.into(),
+            PixelType::U8x3 => ImageView::<U8x3>::try_new(width, height, buffer)?.into(),
+            PixelType::U8x4 => ImageView::<U8x4>::try_new(width, height, buffer)?.into(),
+            PixelType::U16 => ImageView::<U16>::try_new(width, height, buffer)?.into(),
+            PixelType::U16x2 => ImageView::<U16x2>::try_new(width, height, buffer)?.into(),
+            PixelType::U16x3 => ImageView::<U16x3>::try_new(width, height, buffer)?.into(),
+            PixelType::U16x4 => ImageView::<U16x4>::try_new(width, height, buffer)?.into(),
+            PixelType::I32 => ImageView::<I32>::try_new(width, height, buffer)?.into(),
+            PixelType::F32 => ImageView::<F32>::try_new(width, height, buffer)?.into(),
+            PixelType::F32x2 => ImageView::<F32x2>::try_new(width, height, buffer)?.into(),
+            PixelType::F32x3 => ImageView::<F32x3>::try_new(width, height, buffer)?.into(),
+            PixelType::F32x4 => ImageView::<F32x4>::try_new(width, height, buffer)?.into(),
+        })
+    }
+
+    /// Creates view of buffer with pixels of given type and rows
+    /// which start every `stride` bytes.
+    pub fn try_with_stride(
+        width: NonZeroU32,
+        height: NonZeroU32,
+        stride: usize,
+        buffer: &'a [u8],
+        pixel_type: PixelType,
+    ) -> Result<Self, Error> {
+        Ok(match pixel_type {
+            PixelType::U8 => {
+                ImageView::<U8>::try_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::U8x2 => {
+                ImageView::<U8x2>::try_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::U8x3 => {
+                ImageView::<U8x3>::try_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::U8x4 => {
+                ImageView::<U8x4>::try_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::U16 => {
+                ImageView::<U16>::try_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::U16x2 => {
+                ImageView::<U16x2>::try_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::U16x3 => {
+                ImageView::<U16x3>::try_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::U16x4 => {
+                ImageView::<U16x4>::try_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::I32 => {
+                ImageView::<I32>::try_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::F32 => {
+                ImageView::<F32>::try_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::F32x2 => {
+                ImageView::<F32x2>::try_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::F32x3 => {
+                ImageView::<F32x3>::try_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::F32x4 => {
+                ImageView::<F32x4>::try_with_stride(width, height, stride, buffer)?.into()
+            }
+        })
+    }
+
+    pub fn pixel_type(&self) -> PixelType {
+        match self {
+            Self::U8(_) => PixelType::U8,
+            Self::U8x2(_) => PixelType::U8x2,
+            Self::U8x3(_) => PixelType::U8x3,
+            Self::U8x4(_) => PixelType::U8x4,
+            Self::U16(_) => PixelType::U16,
+            Self::U16x2(_) => PixelType::U16x2,
+            Self::U16x3(_) => PixelType::U16x3,
+            Self::U16x4(_) => PixelType::U16x4,
+            Self::I32(_) => PixelType::I32,
+            Self::F32(_) => PixelType::F32,
+            Self::F32x2(_) => PixelType::F32x2,
+            Self::F32x3(_) => PixelType::F32x3,
+            Self::F32x4(_) => PixelType::F32x4,
+        }
+    }
+
+    pub fn width(&self) -> NonZeroU32 {
+        dynamic_map!(Self, self, |view| view.width())
+    }
+
+    pub fn height(&self) -> NonZeroU32 {
+        dynamic_map!(Self, self, |view| view.height())
+    }
+
+    pub fn crop_box(&self) -> CropBox {
+        dynamic_map!(Self, self, |view| view.crop_box())
+    }
+
+    /// # Safety
+    /// The crop box must be inside of the image boundaries.
+    pub unsafe fn set_crop_box(&mut self, crop_box: CropBox) {
+        dynamic_map!(Self, self, |view| view.set_crop_box(crop_box))
+    }
+
+    /// Checked version of [`DynamicImageView::set_crop_box`].
+    pub fn try_set_crop_box(&mut self, crop_box: CropBox) -> Result<(), Error> {
+        dynamic_map!(Self, self, |view| view.try_set_crop_box(crop_box))
+    }
+
+    /// Returns view of the region of image selected by given crop box.
+    ///
+    /// See [`ImageView::sub_view`].
+    ///
+    /// # Safety
+    /// The crop box must be inside of the image boundaries.
+    pub unsafe fn sub_view(&self, crop_box: CropBox) -> DynamicImageView<'a> {
+        dynamic_map!(Self, self, |view| view.sub_view(crop_box).into())
+    }
+
+    /// Checked version of [`DynamicImageView::sub_view`].
+    pub fn try_sub_view(&self, crop_box: CropBox) -> Result<DynamicImageView<'a>, Error> {
+        dynamic_map!(Self, self, |view| Ok(view.try_sub_view(crop_box)?.into()))
+    }
+
+    /// Create cropped version of the view.
+    ///
+    /// # Safety
+    /// The crop box must be inside of the image boundaries.
+    pub unsafe fn crop(self, crop_box: CropBox) -> Self {
+        dynamic_map!(Self, self, |view| view.crop(crop_box).into())
+    }
+
+    /// Checked version of [`DynamicImageView::crop`].
+    pub fn try_crop(self, crop_box: CropBox) -> Result<Self, Error> {
+        dynamic_map!(Self, self, |view| Ok(view.try_crop(crop_box)?.into()))
+    }
+
+    /// Set a crop box to resize the source image into the
+    /// aspect ratio of destination image without distortions.
+    ///
+    /// See [`ImageView::set_crop_box_to_fit_dst_size`].
+    pub fn set_crop_box_to_fit_dst_size(
+        &mut self,
+        dst_width: NonZeroU32,
+        dst_height: NonZeroU32,
+        centering: Option<(f32, f32)>,
+    ) {
+        dynamic_map!(Self, self, |view| view
+            .set_crop_box_to_fit_dst_size(dst_width, dst_height, centering))
+    }
+}
+
+impl<'a> DynamicImageViewMut<'a> {
+    /// Creates mutable view of buffer with pixels of given type.
+    pub fn try_new(
+        width: NonZeroU32,
+        height: NonZeroU32,
+        buffer: &'a mut [u8],
+        pixel_type: PixelType,
+    ) -> Result<Self, Error> {
+        Ok(match pixel_type {
+            PixelType::U8 => ImageViewMut::<U8>::try_new(width, height, buffer)?.into(),
+            PixelType::U8x2 => ImageViewMut::<U8x2>::try_new(width, height, buffer)?.into(),
+            PixelType::U8x3 => ImageViewMut::<U8x3>::try_new(width, height, buffer)?.into(),
+            PixelType::U8x4 => ImageViewMut::<U8x4>::try_new(width, height, buffer)?.into(),
+            PixelType::U16 => ImageViewMut::<U16>::try_new(width, height, buffer)?.into(),
+            PixelType::U16x2 => ImageViewMut::<U16x2>::try_new(width, height, buffer)?.into(),
+            PixelType::U16x3 => ImageViewMut::<U16x3>::try_new(width, height, buffer)?.into(),
+            PixelType::U16x4 => ImageViewMut::<U16x4>::try_new(width, height, buffer)?.into(),
+            PixelType::I32 => ImageViewMut::<I32>::try_new(width, height, buffer)?.into(),
+            PixelType::F32 => ImageViewMut::<F32>::try_new(width, height, buffer)?.into(),
+            PixelType::F32x2 => ImageViewMut::<F32x2>::try_new(width, height, buffer)?.into(),
+            PixelType::F32x3 => ImageViewMut::<F32x3>::try_new(width, height, buffer)?.into(),
+            PixelType::F32x4 => ImageViewMut::<F32x4>::try_new(width, height, buffer)?.into(),
+        })
+    }
+
+    /// Creates mutable view of buffer with pixels of given type
+    /// and rows which start every `stride` bytes.
+    pub fn try_with_stride(
+        width: NonZeroU32,
+        height: NonZeroU32,
+        stride: usize,
+        buffer: &'a mut [u8],
+        pixel_type: PixelType,
+    ) -> Result<Self, Error> {
+        Ok(match pixel_type {
+            PixelType::U8 => {
+                ImageViewMut::<U8>::try_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::U8x2 => {
+                ImageViewMut::<U8x2>::try_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::U8x3 => {
+                ImageViewMut::<U8x3>::try_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::U8x4 => {
+                ImageViewMut::<U8x4>::try_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::U16 => {
+                ImageViewMut::<U16>::try_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::U16x2 => {
+                ImageViewMut::<U16x2>::try_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::U16x3 => {
+                ImageViewMut::<U16x3>::try_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::U16x4 => {
+                ImageViewMut::<U16x4>::try_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::I32 => {
+                ImageViewMut::<I32>::try_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::F32 => {
+                ImageViewMut::<F32>::try_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::F32x2 => {
+                ImageViewMut::<F32x2>::try_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::F32x3 => {
+                ImageViewMut::<F32x3>::try_with_stride(width, height, stride, buffer)?.into()
+            }
+            PixelType::F32x4 => {
+                ImageViewMut::<F32x4>::try_with_stride(width, height, stride, buffer)?.into()
+            }
+        })
+    }
+
+    pub fn pixel_type(&self) -> PixelType {
+        match self {
+            Self::U8(_) => PixelType::U8,
+            Self::U8x2(_) => PixelType::U8x2,
+            Self::U8x3(_) => PixelType::U8x3,
+            Self::U8x4(_) => PixelType::U8x4,
+            Self::U16(_) => PixelType::U16,
+            Self::U16x2(_) => PixelType::U16x2,
+            Self::U16x3(_) => PixelType::U16x3,
+            Self::U16x4(_) => PixelType::U16x4,
+            Self::I32(_) => PixelType::I32,
+            Self::F32(_) => PixelType::F32,
+            Self::F32x2(_) => PixelType::F32x2,
+            Self::F32x3(_) => PixelType::F32x3,
+            Self::F32x4(_) => PixelType::F32x4,
+        }
+    }
+
+    pub fn width(&self) -> NonZeroU32 {
+        dynamic_map!(Self, self, |view| view.width())
+    }
+
+    pub fn height(&self) -> NonZeroU32 {
+        dynamic_map!(Self, self, |view| view.height())
+    }
+
+    /// Create cropped version of the view.
+    ///
+    /// # Safety
+    /// The crop box must be inside of the image boundaries.
+    pub unsafe fn crop(self, crop_box: CropBox) -> Self {
+        dynamic_map!(Self, self, |view| view.crop(crop_box).into())
+    }
+
+    /// Checked version of [`DynamicImageViewMut::crop`].
+    pub fn try_crop(self, crop_box: CropBox) -> Result<Self, Error> {
+        dynamic_map!(Self, self, |view| Ok(view.try_crop(crop_box)?.into()))
+    }
+}
+
+impl<'a> From<DynamicImageViewMut<'a>> for DynamicImageView<'a> {
+    fn from(view: DynamicImageViewMut<'a>) -> Self {
+        dynamic_map!(DynamicImageViewMut, view, |view| ImageView::from(view)
+            .into())
+    }
+}
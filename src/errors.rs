@@ -0,0 +1,55 @@
+use std::error;
+use std::fmt::{Display, Formatter};
+
+/// Errors returned by checked (`try_*`) versions of methods.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// Size of buffer is smaller than required for image with given dimensions.
+    InvalidBufferSize,
+    /// Buffer is not aligned to the size of pixel's component.
+    InvalidBufferAlignment,
+    /// Stride of rows is smaller than size of row of image or is not
+    /// a multiple of the alignment of pixel's components.
+    InvalidStride,
+    /// Crop box is out of the image boundaries.
+    CropBoxOutOfBounds,
+    /// Crop box has non-integer boundaries where only whole
+    /// pixels can be selected.
+    NonIntegerCropBox,
+    /// Destination image is out of the boundaries of virtual
+    /// destination image.
+    DstWindowOutOfBounds,
+    /// Sizes of source and destination images are not equal.
+    SizeMismatch,
+    /// Source and destination images have different types of pixels.
+    DifferentTypesOfPixels,
+    /// Support of filter is not a finite positive value.
+    InvalidFilterSupport,
+    /// Blur factor is not a finite positive value.
+    InvalidBlurFactor,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let msg = match self {
+            Self::InvalidBufferSize => "Size of buffer is smaller than required",
+            Self::InvalidBufferAlignment => "Buffer is not aligned to the pixel type",
+            Self::InvalidStride => "Stride of rows is not valid for the pixel type",
+            Self::CropBoxOutOfBounds => "Crop box is out of the image boundaries",
+            Self::NonIntegerCropBox => "Crop box has non-integer boundaries",
+            Self::DstWindowOutOfBounds => {
+                "Destination image is out of the boundaries of virtual image"
+            }
+            Self::SizeMismatch => "Sizes of source and destination images are not equal",
+            Self::DifferentTypesOfPixels => {
+                "Source and destination images have different types of pixels"
+            }
+            Self::InvalidFilterSupport => "Support of filter is not a finite positive value",
+            Self::InvalidBlurFactor => "Blur factor is not a finite positive value",
+        };
+        f.write_str(msg)
+    }
+}
+
+impl error::Error for Error {}
@@ -1,8 +1,9 @@
 use std::marker::PhantomData;
 use std::num::NonZeroU32;
 
+use crate::image_view::{check_buffer_alignment, check_buffer_size, check_buffer_size_with_stride};
 use crate::pixels::PixelExt;
-use crate::{error, ImageView, ImageViewMut};
+use crate::{error, Error, ImageView, ImageViewMut};
 
 #[derive(Debug)]
 enum BufferContainer<'a> {
@@ -24,6 +25,8 @@ impl<'a> BufferContainer<'a> {
 pub struct Image<'a, P: PixelExt> {
     width: NonZeroU32,
     height: NonZeroU32,
+    /// Distance in bytes between starts of adjacent rows.
+    stride: usize,
     buffer: BufferContainer<'a>,
     pixel_type: PhantomData<P>,
 }
@@ -36,6 +39,7 @@ impl<'a, P: PixelExt> Image<'a, P> {
         Self {
             width,
             height,
+            stride: width.get() as usize * P::size(),
             buffer,
             pixel_type: PhantomData,
         }
@@ -44,16 +48,33 @@ impl<'a, P: PixelExt> Image<'a, P> {
     pub unsafe fn from_vec_u8(width: NonZeroU32, height: NonZeroU32, buffer: Vec<u8>) -> Self {
         let size = (width.get() * height.get()) as usize * P::size();
         if buffer.len() < size {
-            error!();
+            error!(
+                "Size of buffer ({} bytes) is smaller than required for {}x{} image ({} bytes)",
+                buffer.len(),
+                width,
+                height,
+                size
+            );
         }
         Self {
             width,
             height,
+            stride: width.get() as usize * P::size(),
             buffer: BufferContainer::VecU8(buffer),
             pixel_type: PhantomData,
         }
     }
 
+    /// Checked version of [`Image::from_vec_u8`].
+    pub fn try_from_vec_u8(
+        width: NonZeroU32,
+        height: NonZeroU32,
+        buffer: Vec<u8>,
+    ) -> Result<Self, Error> {
+        check_buffer_size::<P>(width, height, buffer.len())?;
+        Ok(unsafe { Self::from_vec_u8(width, height, buffer) })
+    }
+
     pub unsafe fn from_slice_u8(
         width: NonZeroU32,
         height: NonZeroU32,
@@ -61,21 +82,120 @@ impl<'a, P: PixelExt> Image<'a, P> {
     ) -> Self {
         let size = (width.get() * height.get()) as usize * P::size();
         if buffer.len() < size {
-            error!();
+            error!(
+                "Size of buffer ({} bytes) is smaller than required for {}x{} image ({} bytes)",
+                buffer.len(),
+                width,
+                height,
+                size
+            );
+        }
+        Self {
+            width,
+            height,
+            stride: width.get() as usize * P::size(),
+            buffer: BufferContainer::MutU8(buffer),
+            pixel_type: PhantomData,
+        }
+    }
+
+    /// Checked version of [`Image::from_slice_u8`].
+    pub fn try_from_slice_u8(
+        width: NonZeroU32,
+        height: NonZeroU32,
+        buffer: &'a mut [u8],
+    ) -> Result<Self, Error> {
+        check_buffer_size::<P>(width, height, buffer.len())?;
+        Ok(unsafe { Self::from_slice_u8(width, height, buffer) })
+    }
+
+    /// Create image from buffer with rows which start every `stride` bytes.
+    ///
+    /// # Safety
+    /// `stride` must be a multiple of pixel alignment and not less than
+    /// the size of row, `buffer` must contain all rows.
+    pub unsafe fn from_vec_u8_with_stride(
+        width: NonZeroU32,
+        height: NonZeroU32,
+        stride: usize,
+        buffer: Vec<u8>,
+    ) -> Self {
+        if check_buffer_size_with_stride::<P>(width, height, stride, buffer.len()).is_err() {
+            error!(
+                "Stride ({} bytes) or size of buffer ({} bytes) is not valid for {}x{} image",
+                stride,
+                buffer.len(),
+                width,
+                height
+            );
+        }
+        Self {
+            width,
+            height,
+            stride,
+            buffer: BufferContainer::VecU8(buffer),
+            pixel_type: PhantomData,
+        }
+    }
+
+    /// Checked version of [`Image::from_vec_u8_with_stride`].
+    pub fn try_from_vec_u8_with_stride(
+        width: NonZeroU32,
+        height: NonZeroU32,
+        stride: usize,
+        buffer: Vec<u8>,
+    ) -> Result<Self, Error> {
+        check_buffer_size_with_stride::<P>(width, height, stride, buffer.len())?;
+        Ok(unsafe { Self::from_vec_u8_with_stride(width, height, stride, buffer) })
+    }
+
+    /// Create image from mutable slice with rows which start
+    /// every `stride` bytes.
+    ///
+    /// # Safety
+    /// `stride` must be a multiple of pixel alignment and not less than
+    /// the size of row, `buffer` must contain all rows.
+    pub unsafe fn from_slice_u8_with_stride(
+        width: NonZeroU32,
+        height: NonZeroU32,
+        stride: usize,
+        buffer: &'a mut [u8],
+    ) -> Self {
+        if check_buffer_size_with_stride::<P>(width, height, stride, buffer.len()).is_err() {
+            error!(
+                "Stride ({} bytes) or size of buffer ({} bytes) is not valid for {}x{} image",
+                stride,
+                buffer.len(),
+                width,
+                height
+            );
         }
         Self {
             width,
             height,
+            stride,
             buffer: BufferContainer::MutU8(buffer),
             pixel_type: PhantomData,
         }
     }
 
+    /// Checked version of [`Image::from_slice_u8_with_stride`].
+    pub fn try_from_slice_u8_with_stride(
+        width: NonZeroU32,
+        height: NonZeroU32,
+        stride: usize,
+        buffer: &'a mut [u8],
+    ) -> Result<Self, Error> {
+        check_buffer_size_with_stride::<P>(width, height, stride, buffer.len())?;
+        Ok(unsafe { Self::from_slice_u8_with_stride(width, height, stride, buffer) })
+    }
+
     /// Creates a copy of the image.
     pub fn copy(&self) -> Image<'static, P> {
         Image {
             width: self.width,
             height: self.height,
+            stride: self.stride,
             buffer: BufferContainer::VecU8(self.buffer.as_vec()),
             pixel_type: self.pixel_type,
         }
@@ -91,6 +211,12 @@ impl<'a, P: PixelExt> Image<'a, P> {
         self.height
     }
 
+    /// Distance in bytes between starts of adjacent rows of the image.
+    #[inline(always)]
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
     /// Buffer with image pixels.
     #[inline(always)]
     pub fn buffer(&self) -> &[u8] {
@@ -119,12 +245,30 @@ impl<'a, P: PixelExt> Image<'a, P> {
 
     #[inline(always)]
     pub unsafe fn view(&self) -> ImageView<P> {
-        ImageView::new(self.width, self.height, self.buffer())
+        ImageView::with_stride(self.width, self.height, self.stride, self.buffer())
     }
 
     #[inline(always)]
     pub unsafe fn view_mut(&mut self) -> ImageViewMut<P> {
-        ImageViewMut::new(self.width, self.height, self.buffer_mut())
+        let (width, height, stride) = (self.width, self.height, self.stride);
+        ImageViewMut::with_stride(width, height, stride, self.buffer_mut())
+    }
+
+    /// Checked version of [`Image::view`].
+    #[inline(always)]
+    pub fn try_view(&self) -> Result<ImageView<'_, P>, Error> {
+        let buffer = self.buffer();
+        check_buffer_alignment::<P>(buffer.as_ptr())?;
+        Ok(unsafe { ImageView::with_stride(self.width, self.height, self.stride, buffer) })
+    }
+
+    /// Checked version of [`Image::view_mut`].
+    #[inline(always)]
+    pub fn try_view_mut(&mut self) -> Result<ImageViewMut<'_, P>, Error> {
+        let (width, height, stride) = (self.width, self.height, self.stride);
+        let buffer = self.buffer_mut();
+        check_buffer_alignment::<P>(buffer.as_ptr())?;
+        Ok(unsafe { ImageViewMut::with_stride(width, height, stride, buffer) })
     }
 }
 
@@ -1,22 +1,50 @@
 use std::fmt::Debug;
-use std::mem::ManuallyDrop;
+use std::marker::PhantomData;
 use std::num::NonZeroU32;
+use std::ops::Range;
+use std::ptr::NonNull;
 use std::slice;
 
-use crate::error;
 use crate::pixels::{GetCount, IntoPixelComponent, PixelComponent, PixelExt};
+use crate::{error, Error};
 
 /// Parameters of crop box that may be used with [`ImageView`]
 /// and [`DynamicImageView`](crate::DynamicImageView)
-#[derive(Debug, Clone, Copy)]
+///
+/// Boundaries of crop box may be fractional to select
+/// a sub-pixel region of source image.
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct CropBox {
-    pub left: u32,
-    pub top: u32,
-    pub width: NonZeroU32,
-    pub height: NonZeroU32,
+    pub left: f64,
+    pub top: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl CropBox {
+    /// Returns crop box which covers the whole image with given size.
+    pub(crate) fn full(width: NonZeroU32, height: NonZeroU32) -> Self {
+        Self {
+            left: 0.,
+            top: 0.,
+            width: width.get() as f64,
+            height: height.get() as f64,
+        }
+    }
+
+    /// Returns `true` if all boundaries of crop box are integers.
+    pub(crate) fn is_integer(&self) -> bool {
+        [self.left, self.top, self.width, self.height]
+            .iter()
+            .all(|v| v.fract() == 0.)
+    }
 }
 
 /// Generic immutable image view.
+///
+/// View doesn't own pixels, it stores pointer to the first pixel,
+/// dimensions and distance between starts of rows. So creating,
+/// cropping and converting of views doesn't allocate memory.
 #[derive(Debug, Clone)]
 pub struct ImageView<'a, P>
 where
@@ -25,9 +53,15 @@ where
     width: NonZeroU32,
     height: NonZeroU32,
     crop_box: CropBox,
-    rows: Vec<&'a [P]>,
+    ptr: NonNull<P>,
+    /// Distance between starts of rows in bytes.
+    stride: usize,
+    phantom: PhantomData<&'a [P]>,
 }
 
+unsafe impl<P: PixelExt + Sync> Send for ImageView<'_, P> {}
+unsafe impl<P: PixelExt + Sync> Sync for ImageView<'_, P> {}
+
 impl<'a, P> ImageView<'a, P>
 where
     P: PixelExt,
@@ -35,47 +69,114 @@ where
     pub unsafe fn new(width: NonZeroU32, height: NonZeroU32, buffer: &'a [u8]) -> Self {
         let size = (width.get() * height.get()) as usize * P::size();
         if buffer.len() < size {
-            error!();
-        }
-        let rows_count = height.get() as usize;
-        let pixels = unsafe { align_buffer_to(buffer) };
-        let rows = pixels
-            .chunks_exact(width.get() as usize)
-            .take(rows_count)
-            .collect();
-        Self {
-            width,
-            height,
-            crop_box: CropBox {
-                left: 0,
-                top: 0,
+            error!(
+                "Size of buffer ({} bytes) is smaller than required for {}x{} image ({} bytes)",
+                buffer.len(),
                 width,
                 height,
-            },
-            rows,
+                size
+            );
         }
+        let ptr = unsafe { aligned_ptr(buffer) };
+        unsafe { Self::from_raw_parts(width, height, ptr, width.get() as usize * P::size()) }
+    }
+
+    /// Checked version of [`ImageView::new`].
+    pub fn try_new(width: NonZeroU32, height: NonZeroU32, buffer: &'a [u8]) -> Result<Self, Error> {
+        check_buffer_size::<P>(width, height, buffer.len())?;
+        check_buffer_alignment::<P>(buffer.as_ptr())?;
+        Ok(unsafe { Self::new(width, height, buffer) })
     }
 
     pub unsafe fn from_pixels(width: NonZeroU32, height: NonZeroU32, pixels: &'a [P]) -> Self {
         let size = (width.get() * height.get()) as usize;
         if pixels.len() < size {
-            error!()
+            error!(
+                "Count of pixels ({}) is smaller than required for {}x{} image ({})",
+                pixels.len(),
+                width,
+                height,
+                size
+            );
         }
-        let rows_count = height.get() as usize;
-        let rows = pixels
-            .chunks_exact(width.get() as usize)
-            .take(rows_count)
-            .collect();
+        let ptr = NonNull::from(pixels).cast();
+        unsafe { Self::from_raw_parts(width, height, ptr, width.get() as usize * P::size()) }
+    }
+
+    /// Checked version of [`ImageView::from_pixels`].
+    pub fn try_from_pixels(
+        width: NonZeroU32,
+        height: NonZeroU32,
+        pixels: &'a [P],
+    ) -> Result<Self, Error> {
+        check_buffer_size::<P>(width, height, pixels.len() * P::size())?;
+        Ok(unsafe { Self::from_pixels(width, height, pixels) })
+    }
+
+    /// Creates view of buffer with rows which start every `stride` bytes.
+    /// Bytes between the end of row and the start of next row are ignored.
+    ///
+    /// # Safety
+    /// `stride` must be a multiple of pixel alignment and not less than
+    /// the size of row, `buffer` must be aligned and contain all rows.
+    /// See [`ImageView::try_with_stride`] for the checked version.
+    pub unsafe fn with_stride(
+        width: NonZeroU32,
+        height: NonZeroU32,
+        stride: usize,
+        buffer: &'a [u8],
+    ) -> Self {
+        let row_size = width.get() as usize * P::size();
+        if stride < row_size {
+            error!(
+                "Stride ({} bytes) is smaller than size of row of {}x{} image ({} bytes)",
+                stride, width, height, row_size
+            );
+        }
+        let size = stride * (height.get() as usize - 1) + row_size;
+        if buffer.len() < size {
+            error!(
+                "Size of buffer ({} bytes) is smaller than required for {}x{} image ({} bytes)",
+                buffer.len(),
+                width,
+                height,
+                size
+            );
+        }
+        // Rows may start at offsets which are not multiples of the pixel
+        // size, so the pointer must be derived from all bytes of rows.
+        let ptr = unsafe { aligned_ptr(&buffer[..size]) };
+        unsafe { Self::from_raw_parts(width, height, ptr, stride) }
+    }
+
+    /// Checked version of [`ImageView::with_stride`].
+    pub fn try_with_stride(
+        width: NonZeroU32,
+        height: NonZeroU32,
+        stride: usize,
+        buffer: &'a [u8],
+    ) -> Result<Self, Error> {
+        check_buffer_size_with_stride::<P>(width, height, stride, buffer.len())?;
+        check_buffer_alignment::<P>(buffer.as_ptr())?;
+        Ok(unsafe { Self::with_stride(width, height, stride, buffer) })
+    }
+
+    /// Caller must guarantee that `ptr` points to the first row and
+    /// all rows which start every `stride` bytes are inside of the
+    /// memory which `ptr` was derived from.
+    unsafe fn from_raw_parts(
+        width: NonZeroU32,
+        height: NonZeroU32,
+        ptr: NonNull<P>,
+        stride: usize,
+    ) -> Self {
         Self {
             width,
             height,
-            crop_box: CropBox {
-                left: 0,
-                top: 0,
-                width,
-                height,
-            },
-            rows,
+            crop_box: CropBox::full(width, height),
+            ptr,
+            stride,
+            phantom: PhantomData,
         }
     }
 
@@ -91,18 +192,89 @@ where
         self.crop_box
     }
 
+    /// Set a crop box that is used by [`Resizer`](crate::Resizer)
+    /// to select a region of the source image. Other operations
+    /// process the whole view, use [`ImageView::sub_view`] to restrict
+    /// all of them.
+    ///
+    /// # Safety
+    /// The crop box must be inside of the image boundaries.
     pub unsafe fn set_crop_box(&mut self, crop_box: CropBox) {
-        if crop_box.left >= self.width.get() || crop_box.top >= self.height.get() {
-            error!();
-        }
-        let right = crop_box.left + crop_box.width.get();
-        let bottom = crop_box.top + crop_box.height.get();
-        if right > self.width.get() || bottom > self.height.get() {
-            error!();
+        if check_crop_box(self.width, self.height, crop_box).is_err() {
+            error!(
+                "Crop box {:?} is out of the boundaries of {}x{} image",
+                crop_box, self.width, self.height
+            );
         }
         self.crop_box = crop_box;
     }
 
+    /// Checked version of [`ImageView::set_crop_box`].
+    pub fn try_set_crop_box(&mut self, crop_box: CropBox) -> Result<(), Error> {
+        check_crop_box(self.width, self.height, crop_box)?;
+        self.crop_box = crop_box;
+        Ok(())
+    }
+
+    /// Returns view of the region of image selected by given crop box.
+    ///
+    /// Unlike [`ImageView::set_crop_box`] the returned view contains
+    /// only pixels of the region, so all operations are restricted by it.
+    /// Boundaries of the crop box must be integers. Crop box of the
+    /// returned view covers the whole view.
+    ///
+    /// # Safety
+    /// The crop box must be inside of the image boundaries.
+    pub unsafe fn sub_view(&self, crop_box: CropBox) -> ImageView<'a, P> {
+        if check_crop_box(self.width, self.height, crop_box).is_err() {
+            error!(
+                "Crop box {:?} is out of the boundaries of {}x{} image",
+                crop_box, self.width, self.height
+            );
+        }
+        if !crop_box.is_integer() {
+            error!("Crop box {:?} has non-integer boundaries", crop_box);
+        }
+        let width = NonZeroU32::new(crop_box.width as u32).unwrap();
+        let height = NonZeroU32::new(crop_box.height as u32).unwrap();
+        let ptr = unsafe {
+            self.row_ptr(crop_box.top as usize)
+                .add(crop_box.left as usize)
+        };
+        ImageView {
+            width,
+            height,
+            crop_box: CropBox::full(width, height),
+            ptr: unsafe { NonNull::new_unchecked(ptr) },
+            stride: self.stride,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Checked version of [`ImageView::sub_view`].
+    pub fn try_sub_view(&self, crop_box: CropBox) -> Result<ImageView<'a, P>, Error> {
+        check_crop_box(self.width, self.height, crop_box)?;
+        if !crop_box.is_integer() {
+            return Err(Error::NonIntegerCropBox);
+        }
+        Ok(unsafe { self.sub_view(crop_box) })
+    }
+
+    /// Create cropped version of the view.
+    ///
+    /// See [`ImageView::sub_view`].
+    ///
+    /// # Safety
+    /// The crop box must be inside of the image boundaries.
+    pub unsafe fn crop(self, crop_box: CropBox) -> Self {
+        unsafe { self.sub_view(crop_box) }
+    }
+
+    /// Checked version of [`ImageView::crop`].
+    pub fn try_crop(self, crop_box: CropBox) -> Result<Self, Error> {
+        self.try_sub_view(crop_box)
+    }
+
     /// Set a crop box to resize the source image into the
     /// aspect ratio of destination image without distortions.
     ///
@@ -130,27 +302,29 @@ where
             (0.5, 0.5)
         };
 
+        let centering = (centering.0 as f64, centering.1 as f64);
+
         // calculate aspect ratios
-        let width = self.width.get() as f32;
-        let height = self.height.get() as f32;
+        let width = self.width.get() as f64;
+        let height = self.height.get() as f64;
         let image_ratio = width / height;
-        let required_ration = dst_width.get() as f32 / dst_height.get() as f32;
+        let required_ration = dst_width.get() as f64 / dst_height.get() as f64;
 
         let crop_width;
         let crop_height;
         // figure out if the sides or top/bottom will be cropped off
-        if (image_ratio - required_ration).abs() < f32::EPSILON {
+        if (image_ratio - required_ration).abs() < f64::EPSILON {
             // The image is already the needed ratio
             crop_width = width;
             crop_height = height;
         } else if image_ratio >= required_ration {
             // The image is wider than what's needed, crop the sides
-            crop_width = required_ration * height;
+            crop_width = (required_ration * height).min(width);
             crop_height = height;
         } else {
             // The image is taller than what's needed, crop the top and bottom
             crop_width = width;
-            crop_height = width / required_ration;
+            crop_height = (width / required_ration).min(height);
         }
 
         let crop_left = (width - crop_width) * centering.0;
@@ -158,14 +332,26 @@ where
 
         unsafe {
             self.set_crop_box(CropBox {
-                left: crop_left.round() as u32,
-                top: crop_top.round() as u32,
-                width: NonZeroU32::new(crop_width.round() as u32).unwrap(),
-                height: NonZeroU32::new(crop_height.round() as u32).unwrap(),
+                left: crop_left,
+                top: crop_top,
+                width: crop_width,
+                height: crop_height,
             })
         };
     }
 
+    /// Returns pointer to the first pixel of row with given index.
+    #[inline(always)]
+    unsafe fn row_ptr(&self, y: usize) -> *mut P {
+        unsafe { self.ptr.as_ptr().cast::<u8>().add(y * self.stride).cast() }
+    }
+
+    /// Returns row with given index without checking of bounds.
+    #[inline(always)]
+    unsafe fn row_unchecked(&self, y: usize) -> &'a [P] {
+        unsafe { slice::from_raw_parts(self.row_ptr(y), self.width.get() as usize) }
+    }
+
     #[inline(always)]
     pub(crate) fn iter_4_rows<'s>(
         &'s self,
@@ -174,10 +360,17 @@ where
     ) -> impl Iterator<Item = [&'a [P]; 4]> + 's {
         let start_y = start_y as usize;
         let max_y = max_y.min(self.height.get()) as usize;
-        let rows = self.rows.get(start_y..max_y).unwrap_or(&[]);
-        rows.chunks_exact(4).map(|rows| match *rows {
-            [r0, r1, r2, r3] => [r0, r1, r2, r3],
-            _ => unreachable!(),
+        let count = max_y.saturating_sub(start_y) / 4;
+        (0..count).map(move |i| {
+            let y = start_y + i * 4;
+            unsafe {
+                [
+                    self.row_unchecked(y),
+                    self.row_unchecked(y + 1),
+                    self.row_unchecked(y + 2),
+                    self.row_unchecked(y + 3),
+                ]
+            }
         })
     }
 
@@ -189,44 +382,67 @@ where
     ) -> impl Iterator<Item = [&'a [P]; 2]> + 's {
         let start_y = start_y as usize;
         let max_y = max_y.min(self.height.get()) as usize;
-        let rows = self.rows.get(start_y..max_y).unwrap_or(&[]);
-        rows.chunks_exact(2).map(|rows| match *rows {
-            [r0, r1] => [r0, r1],
-            _ => unreachable!(),
+        let count = max_y.saturating_sub(start_y) / 2;
+        (0..count).map(move |i| {
+            let y = start_y + i * 2;
+            unsafe { [self.row_unchecked(y), self.row_unchecked(y + 1)] }
         })
     }
 
     #[inline(always)]
     pub(crate) fn iter_rows<'s>(&'s self, start_y: u32) -> impl Iterator<Item = &'a [P]> + 's {
-        let start_y = start_y as usize;
-        let rows = self.rows.get(start_y..).unwrap_or(&[]);
-        rows.iter().copied()
+        (start_y as usize..self.height.get() as usize)
+            .map(move |y| unsafe { self.row_unchecked(y) })
     }
 
     #[inline(always)]
     pub(crate) fn get_row(&self, y: u32) -> Option<&'a [P]> {
-        self.rows.get(y as usize).copied()
+        if y < self.height.get() {
+            Some(unsafe { self.row_unchecked(y as usize) })
+        } else {
+            None
+        }
+    }
+
+    /// Returns view of given rows of the image without crop box.
+    pub(crate) fn rows_view(&self, rows: Range<u32>) -> ImageView<'a, P> {
+        assert!(rows.start < rows.end && rows.end <= self.height.get());
+        let height = NonZeroU32::new(rows.end - rows.start).unwrap();
+        // Pointer is derived from pointer of the whole image
+        // to keep access to all rows of the view.
+        let ptr = unsafe { self.row_ptr(rows.start as usize) };
+        ImageView {
+            width: self.width,
+            height,
+            crop_box: CropBox::full(self.width, height),
+            ptr: unsafe { NonNull::new_unchecked(ptr) },
+            stride: self.stride,
+            phantom: PhantomData,
+        }
     }
 
+    /// Returns iterator by rows with indexes `y + step * i`
+    /// for `i` in given range.
     #[inline(always)]
     pub(crate) fn iter_rows_with_step<'s>(
         &'s self,
-        mut y: f64,
+        y: f64,
         step: f64,
-        max_count: usize,
+        indexes: Range<u32>,
     ) -> impl Iterator<Item = &'a [P]> + 's {
-        let steps = (self.height.get() as f64 - y) / step;
-        let steps = (steps.max(0.).ceil() as usize).min(max_count);
-        (0..steps).map(move |_| {
-            // Safety of value of y guaranteed by calculation of steps count
-            let row = unsafe { *self.rows.get_unchecked(y as usize) };
-            y += step;
-            row
-        })
+        let height = self.height.get() as usize;
+        indexes
+            .map(move |i| (y + step * i as f64) as usize)
+            .take_while(move |&y| y < height)
+            // Safety of value of y guaranteed by the previous check
+            .map(move |y| unsafe { self.row_unchecked(y) })
     }
 }
 
 /// Generic mutable image view.
+///
+/// Like [`ImageView`] it stores pointer to the first pixel
+/// instead of references to rows.
 #[derive(Debug)]
 pub struct ImageViewMut<'a, P>
 where
@@ -234,9 +450,15 @@ where
 {
     pub(crate) width: NonZeroU32,
     pub(crate) height: NonZeroU32,
-    rows: Vec<&'a mut [P]>,
+    ptr: NonNull<P>,
+    /// Distance between starts of rows in bytes.
+    stride: usize,
+    phantom: PhantomData<&'a mut [P]>,
 }
 
+unsafe impl<P: PixelExt + Send> Send for ImageViewMut<'_, P> {}
+unsafe impl<P: PixelExt + Sync> Sync for ImageViewMut<'_, P> {}
+
 impl<'a, P> ImageViewMut<'a, P>
 where
     P: PixelExt,
@@ -244,35 +466,118 @@ where
     pub unsafe fn new(width: NonZeroU32, height: NonZeroU32, buffer: &'a mut [u8]) -> Self {
         let size = (width.get() * height.get()) as usize * P::size();
         if buffer.len() < size {
-            error!();
-        }
-        let rows_count = height.get() as usize;
-        let pixels = unsafe { align_buffer_to_mut(buffer) };
-        let rows = pixels
-            .chunks_exact_mut(width.get() as usize)
-            .take(rows_count)
-            .collect();
-        Self {
-            width,
-            height,
-            rows,
+            error!(
+                "Size of buffer ({} bytes) is smaller than required for {}x{} image ({} bytes)",
+                buffer.len(),
+                width,
+                height,
+                size
+            );
         }
+        let ptr = unsafe { aligned_ptr_mut(buffer) };
+        unsafe { Self::from_raw_parts(width, height, ptr, width.get() as usize * P::size()) }
+    }
+
+    /// Checked version of [`ImageViewMut::new`].
+    pub fn try_new(
+        width: NonZeroU32,
+        height: NonZeroU32,
+        buffer: &'a mut [u8],
+    ) -> Result<Self, Error> {
+        check_buffer_size::<P>(width, height, buffer.len())?;
+        check_buffer_alignment::<P>(buffer.as_ptr())?;
+        Ok(unsafe { Self::new(width, height, buffer) })
     }
 
     pub unsafe fn from_pixels(width: NonZeroU32, height: NonZeroU32, pixels: &'a mut [P]) -> Self {
         let size = (width.get() * height.get()) as usize;
         if pixels.len() < size {
-            error!();
+            error!(
+                "Count of pixels ({}) is smaller than required for {}x{} image ({})",
+                pixels.len(),
+                width,
+                height,
+                size
+            );
         }
-        let rows_count = height.get() as usize;
-        let rows = pixels
-            .chunks_exact_mut(width.get() as usize)
-            .take(rows_count)
-            .collect();
+        let ptr = NonNull::from(pixels).cast();
+        unsafe { Self::from_raw_parts(width, height, ptr, width.get() as usize * P::size()) }
+    }
+
+    /// Checked version of [`ImageViewMut::from_pixels`].
+    pub fn try_from_pixels(
+        width: NonZeroU32,
+        height: NonZeroU32,
+        pixels: &'a mut [P],
+    ) -> Result<Self, Error> {
+        check_buffer_size::<P>(width, height, pixels.len() * P::size())?;
+        Ok(unsafe { Self::from_pixels(width, height, pixels) })
+    }
+
+    /// Creates mutable view of buffer with rows which start every `stride`
+    /// bytes. Bytes between the end of row and the start of next row
+    /// are not changed.
+    ///
+    /// # Safety
+    /// `stride` must be a multiple of pixel alignment and not less than
+    /// the size of row, `buffer` must be aligned and contain all rows.
+    /// See [`ImageViewMut::try_with_stride`] for the checked version.
+    pub unsafe fn with_stride(
+        width: NonZeroU32,
+        height: NonZeroU32,
+        stride: usize,
+        buffer: &'a mut [u8],
+    ) -> Self {
+        let row_size = width.get() as usize * P::size();
+        if stride < row_size {
+            error!(
+                "Stride ({} bytes) is smaller than size of row of {}x{} image ({} bytes)",
+                stride, width, height, row_size
+            );
+        }
+        let size = stride * (height.get() as usize - 1) + row_size;
+        if buffer.len() < size {
+            error!(
+                "Size of buffer ({} bytes) is smaller than required for {}x{} image ({} bytes)",
+                buffer.len(),
+                width,
+                height,
+                size
+            );
+        }
+        // Rows may start at offsets which are not multiples of the pixel
+        // size, so the pointer must be derived from all bytes of rows.
+        let ptr = unsafe { aligned_ptr_mut(&mut buffer[..size]) };
+        unsafe { Self::from_raw_parts(width, height, ptr, stride) }
+    }
+
+    /// Checked version of [`ImageViewMut::with_stride`].
+    pub fn try_with_stride(
+        width: NonZeroU32,
+        height: NonZeroU32,
+        stride: usize,
+        buffer: &'a mut [u8],
+    ) -> Result<Self, Error> {
+        check_buffer_size_with_stride::<P>(width, height, stride, buffer.len())?;
+        check_buffer_alignment::<P>(buffer.as_ptr())?;
+        Ok(unsafe { Self::with_stride(width, height, stride, buffer) })
+    }
+
+    /// Caller must guarantee that `ptr` points to the first row and
+    /// all rows which start every `stride` bytes are inside of the
+    /// memory which `ptr` was derived from.
+    unsafe fn from_raw_parts(
+        width: NonZeroU32,
+        height: NonZeroU32,
+        ptr: NonNull<P>,
+        stride: usize,
+    ) -> Self {
         Self {
             width,
             height,
-            rows,
+            ptr,
+            stride,
+            phantom: PhantomData,
         }
     }
 
@@ -284,50 +589,117 @@ where
         self.height
     }
 
+    /// Returns pointer to the first pixel of row with given index
+    /// without checking of bounds.
+    #[inline(always)]
+    unsafe fn row_ptr(&self, y: usize) -> *mut P {
+        unsafe { self.ptr.as_ptr().cast::<u8>().add(y * self.stride).cast() }
+    }
+
+    /// Returns row with given index without checking of bounds.
+    ///
+    /// Rows don't overlap, so mutable references to different rows
+    /// may exist at the same time.
     #[inline(always)]
-    pub(crate) fn iter_rows_mut(&mut self) -> slice::IterMut<&'a mut [P]> {
-        self.rows.iter_mut()
+    unsafe fn row_unchecked_mut<'s>(&self, y: usize) -> &'s mut [P] {
+        unsafe { slice::from_raw_parts_mut(self.row_ptr(y), self.width.get() as usize) }
     }
 
     #[inline(always)]
-    pub(crate) fn iter_4_rows_mut<'s>(
-        &'s mut self,
-    ) -> impl Iterator<Item = [&'s mut &'a mut [P]; 4]> {
-        self.rows.chunks_exact_mut(4).map(|rows| match rows {
-            [a, b, c, d] => [a, b, c, d],
-            _ => unreachable!(),
+    pub(crate) fn iter_rows_mut<'s>(&'s mut self) -> impl Iterator<Item = &'s mut [P]> {
+        let view: &'s Self = self;
+        (0..view.height.get() as usize).map(move |y| unsafe { view.row_unchecked_mut(y) })
+    }
+
+    #[inline(always)]
+    pub(crate) fn iter_4_rows_mut<'s>(&'s mut self) -> impl Iterator<Item = [&'s mut [P]; 4]> {
+        let view: &'s Self = self;
+        (0..view.height.get() as usize / 4).map(move |i| {
+            let y = i * 4;
+            unsafe {
+                [
+                    view.row_unchecked_mut(y),
+                    view.row_unchecked_mut(y + 1),
+                    view.row_unchecked_mut(y + 2),
+                    view.row_unchecked_mut(y + 3),
+                ]
+            }
         })
     }
 
     #[inline(always)]
-    pub(crate) fn get_row_mut<'s>(&'s mut self, y: u32) -> Option<&'s mut &'a mut [P]> {
-        self.rows.get_mut(y as usize)
+    pub(crate) fn get_row_mut(&mut self, y: u32) -> Option<&mut [P]> {
+        if y < self.height.get() {
+            Some(unsafe { self.row_unchecked_mut(y as usize) })
+        } else {
+            None
+        }
+    }
+
+    /// Returns view of the same pixels which borrows this view.
+    pub(crate) fn reborrow(&mut self) -> ImageViewMut<'_, P> {
+        ImageViewMut {
+            width: self.width,
+            height: self.height,
+            ptr: self.ptr,
+            stride: self.stride,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Splits the view into stripes with given height (the last
+    /// stripe may be lower) that can be processed independently.
+    pub(crate) fn split_by_height(
+        &mut self,
+        stripe_height: NonZeroU32,
+    ) -> Vec<ImageViewMut<'_, P>> {
+        let height = self.height.get();
+        (0..height)
+            .step_by(stripe_height.get() as usize)
+            .map(|y| ImageViewMut {
+                width: self.width,
+                height: NonZeroU32::new(stripe_height.get().min(height - y)).unwrap(),
+                ptr: unsafe { NonNull::new_unchecked(self.row_ptr(y as usize)) },
+                stride: self.stride,
+                phantom: PhantomData,
+            })
+            .collect()
     }
 
     /// Create cropped version of the view.
+    ///
+    /// Boundaries of the crop box must be integers.
     pub unsafe fn crop(self, crop_box: CropBox) -> Self {
-        if crop_box.left >= self.width.get() || crop_box.top >= self.height.get() {
-            error!();
+        if check_crop_box(self.width, self.height, crop_box).is_err() {
+            error!(
+                "Crop box {:?} is out of the boundaries of {}x{} image",
+                crop_box, self.width, self.height
+            );
         }
-        let right = crop_box.left + crop_box.width.get();
-        let bottom = crop_box.top + crop_box.height.get();
-        if right > self.width.get() || bottom > self.height.get() {
-            error!();
+        if !crop_box.is_integer() {
+            error!("Crop box {:?} has non-integer boundaries", crop_box);
         }
-        let row_range = (crop_box.left as usize)..(right as usize);
-        let rows = self
-            .rows
-            .into_iter()
-            .skip(crop_box.top as usize)
-            .take(crop_box.height.get() as usize)
-            .map(|row| unsafe { row.get_unchecked_mut(row_range.clone()) })
-            .collect();
+        let ptr = unsafe {
+            self.row_ptr(crop_box.top as usize)
+                .add(crop_box.left as usize)
+        };
         Self {
-            width: crop_box.width,
-            height: crop_box.height,
-            rows,
+            width: NonZeroU32::new(crop_box.width as u32).unwrap(),
+            height: NonZeroU32::new(crop_box.height as u32).unwrap(),
+            ptr: unsafe { NonNull::new_unchecked(ptr) },
+            stride: self.stride,
+            phantom: PhantomData,
         }
     }
+
+    /// Checked version of [`ImageViewMut::crop`].
+    pub fn try_crop(self, crop_box: CropBox) -> Result<Self, Error> {
+        check_crop_box(self.width, self.height, crop_box)?;
+        if !crop_box.is_integer() {
+            return Err(Error::NonIntegerCropBox);
+        }
+        Ok(unsafe { self.crop(crop_box) })
+    }
 }
 
 impl<'a, P> From<ImageViewMut<'a, P>> for ImageView<'a, P>
@@ -335,40 +707,98 @@ where
     P: PixelExt,
 {
     fn from(view: ImageViewMut<'a, P>) -> Self {
-        let rows = {
-            let mut old_rows = ManuallyDrop::new(view.rows);
-            let (ptr, length, capacity) =
-                (old_rows.as_mut_ptr(), old_rows.len(), old_rows.capacity());
-            unsafe { Vec::from_raw_parts(ptr as *mut &[P], length, capacity) }
-        };
         ImageView {
             width: view.width,
             height: view.height,
-            crop_box: CropBox {
-                left: 0,
-                top: 0,
-                width: view.width,
-                height: view.height,
-            },
-            rows,
+            crop_box: CropBox::full(view.width, view.height),
+            ptr: view.ptr,
+            stride: view.stride,
+            phantom: PhantomData,
         }
     }
 }
 
-unsafe fn align_buffer_to<T>(buffer: &[u8]) -> &[T] {
-    let (head, pixels, _) = unsafe { buffer.align_to::<T>() };
-    if !head.is_empty() {
-        error!();
+/// Checks that buffer with given size in bytes is big enough
+/// to store an image with given dimensions.
+pub(crate) fn check_buffer_size<P: PixelExt>(
+    width: NonZeroU32,
+    height: NonZeroU32,
+    buffer_size: usize,
+) -> Result<(), Error> {
+    let size = (width.get() as usize)
+        .checked_mul(height.get() as usize)
+        .and_then(|pixels_count| pixels_count.checked_mul(P::size()))
+        .ok_or(Error::InvalidBufferSize)?;
+    if buffer_size < size {
+        return Err(Error::InvalidBufferSize);
+    }
+    Ok(())
+}
+
+/// Checks that rows of an image with given dimensions which start
+/// every `stride` bytes fit into buffer with given size in bytes.
+pub(crate) fn check_buffer_size_with_stride<P: PixelExt>(
+    width: NonZeroU32,
+    height: NonZeroU32,
+    stride: usize,
+    buffer_size: usize,
+) -> Result<(), Error> {
+    let row_size = (width.get() as usize)
+        .checked_mul(P::size())
+        .ok_or(Error::InvalidBufferSize)?;
+    // Every row must be aligned as well as the first one
+    if stride < row_size || !stride.is_multiple_of(std::mem::align_of::<P>()) {
+        return Err(Error::InvalidStride);
+    }
+    let size = stride
+        .checked_mul(height.get() as usize - 1)
+        .and_then(|size| size.checked_add(row_size))
+        .ok_or(Error::InvalidBufferSize)?;
+    if buffer_size < size {
+        return Err(Error::InvalidBufferSize);
+    }
+    Ok(())
+}
+
+pub(crate) fn check_buffer_alignment<P: PixelExt>(ptr: *const u8) -> Result<(), Error> {
+    if ptr.align_offset(std::mem::align_of::<P>()) != 0 {
+        return Err(Error::InvalidBufferAlignment);
+    }
+    Ok(())
+}
+
+pub(crate) fn check_crop_box(
+    width: NonZeroU32,
+    height: NonZeroU32,
+    crop_box: CropBox,
+) -> Result<(), Error> {
+    let right = crop_box.left + crop_box.width;
+    let bottom = crop_box.top + crop_box.height;
+    // Negated comparisons also reject NaN values
+    if !(crop_box.left >= 0.
+        && crop_box.top >= 0.
+        && crop_box.width > 0.
+        && crop_box.height > 0.
+        && right <= width.get() as f64
+        && bottom <= height.get() as f64)
+    {
+        return Err(Error::CropBoxOutOfBounds);
+    }
+    Ok(())
+}
+
+unsafe fn aligned_ptr<T>(buffer: &[u8]) -> NonNull<T> {
+    if buffer.as_ptr().align_offset(std::mem::align_of::<T>()) != 0 {
+        error!("Buffer is not aligned to the pixel type");
     }
-    pixels
+    NonNull::from(buffer).cast()
 }
 
-unsafe fn align_buffer_to_mut<T>(buffer: &mut [u8]) -> &mut [T] {
-    let (head, pixels, _) = unsafe { buffer.align_to_mut::<T>() };
-    if !head.is_empty() {
-        error!();
+unsafe fn aligned_ptr_mut<T>(buffer: &mut [u8]) -> NonNull<T> {
+    if buffer.as_ptr().align_offset(std::mem::align_of::<T>()) != 0 {
+        error!("Buffer is not aligned to the pixel type");
     }
-    pixels
+    NonNull::from(buffer).cast()
 }
 
 pub unsafe fn change_type_of_pixel_components<S, D, In, Out, CC>(
@@ -382,10 +812,16 @@ pub unsafe fn change_type_of_pixel_components<S, D, In, Out, CC>(
     D: PixelExt<Component = Out, CountOfComponents = CC>,
 {
     if src_image.width() != dst_image.width() || src_image.height() != dst_image.height() {
-        error!();
+        error!(
+            "Size of source image ({}x{}) is not equal to size of destination image ({}x{})",
+            src_image.width(),
+            src_image.height(),
+            dst_image.width(),
+            dst_image.height()
+        );
     }
 
-    for (s_row, d_row) in src_image.rows.iter().zip(dst_image.rows.iter_mut()) {
+    for (s_row, d_row) in src_image.iter_rows(0).zip(dst_image.iter_rows_mut()) {
         let s_components = S::components(s_row);
         let d_components = D::components_mut(d_row);
         for (&s_comp, d_comp) in s_components.iter().zip(d_components) {
@@ -393,3 +829,173 @@ pub unsafe fn change_type_of_pixel_components<S, D, In, Out, CC>(
         }
     }
 }
+
+/// Checked version of [`change_type_of_pixel_components`].
+pub fn try_change_type_of_pixel_components<S, D, In, Out, CC>(
+    src_image: &ImageView<S>,
+    dst_image: &mut ImageViewMut<D>,
+) -> Result<(), Error>
+where
+    Out: PixelComponent,
+    In: IntoPixelComponent<Out>,
+    CC: GetCount,
+    S: PixelExt<Component = In, CountOfComponents = CC>,
+    D: PixelExt<Component = Out, CountOfComponents = CC>,
+{
+    if src_image.width() != dst_image.width() || src_image.height() != dst_image.height() {
+        return Err(Error::SizeMismatch);
+    }
+    unsafe { change_type_of_pixel_components(src_image, dst_image) };
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pixels::U16;
+
+    #[test]
+    fn test_checked_constructors() {
+        let width = NonZeroU32::new(4).unwrap();
+        let height = NonZeroU32::new(3).unwrap();
+        let buffer = vec![0u16; 13];
+        let bytes = U16::components(unsafe { buffer.align_to::<U16>().1 });
+        let bytes = unsafe { bytes.align_to::<u8>().1 };
+
+        assert!(ImageView::<U16>::try_new(width, height, &bytes[..24]).is_ok());
+        assert_eq!(
+            ImageView::<U16>::try_new(width, height, &bytes[..23]).err(),
+            Some(Error::InvalidBufferSize)
+        );
+        assert_eq!(
+            ImageView::<U16>::try_new(width, height, &bytes[1..25]).err(),
+            Some(Error::InvalidBufferAlignment)
+        );
+
+        let mut view = ImageView::<U16>::try_new(width, height, bytes).unwrap();
+        let crop_box = CropBox {
+            left: 1.,
+            top: 1.,
+            width: 3.,
+            height: 3.,
+        };
+        assert_eq!(
+            view.try_set_crop_box(crop_box),
+            Err(Error::CropBoxOutOfBounds)
+        );
+        for invalid_crop_box in [
+            CropBox {
+                width: 0.,
+                ..crop_box
+            },
+            CropBox {
+                left: -0.5,
+                ..crop_box
+            },
+            CropBox {
+                top: f64::NAN,
+                ..crop_box
+            },
+        ] {
+            assert_eq!(
+                view.try_set_crop_box(invalid_crop_box),
+                Err(Error::CropBoxOutOfBounds)
+            );
+        }
+        let crop_box = CropBox {
+            left: 0.5,
+            top: 0.25,
+            width: 3.,
+            height: 2.5,
+        };
+        assert!(view.try_set_crop_box(crop_box).is_ok());
+        assert_eq!(view.crop_box(), crop_box);
+
+        let mut buffer = vec![0u16; 12];
+        let view_mut = ImageViewMut::<U16>::try_from_pixels(width, height, unsafe {
+            buffer.align_to_mut::<U16>().1
+        })
+        .unwrap();
+        assert_eq!(
+            view_mut.try_crop(crop_box).err(),
+            Some(Error::NonIntegerCropBox)
+        );
+    }
+
+    #[test]
+    fn test_checked_stride() {
+        let width = NonZeroU32::new(3).unwrap();
+        let height = NonZeroU32::new(2).unwrap();
+        let buffer = vec![0u16; 8];
+        let bytes = unsafe { buffer.align_to::<u8>().1 };
+
+        // Rows start at 0 and 10 bytes, the last row takes 6 bytes.
+        assert!(ImageView::<U16>::try_with_stride(width, height, 10, &bytes[..16]).is_ok());
+        assert_eq!(
+            ImageView::<U16>::try_with_stride(width, height, 10, &bytes[..15]).err(),
+            Some(Error::InvalidBufferSize)
+        );
+        assert_eq!(
+            ImageView::<U16>::try_with_stride(width, height, 4, bytes).err(),
+            Some(Error::InvalidStride)
+        );
+        assert_eq!(
+            ImageView::<U16>::try_with_stride(width, height, 7, bytes).err(),
+            Some(Error::InvalidStride)
+        );
+    }
+
+    #[test]
+    fn test_crop_of_strided_view() {
+        let width = NonZeroU32::new(4).unwrap();
+        let height = NonZeroU32::new(5).unwrap();
+        // Rows start every 6 pixels
+        let mut buffer = vec![0u16; 6 * 4 + 4];
+        let bytes = unsafe { buffer.align_to_mut::<u8>().1 };
+        let view = ImageViewMut::<U16>::try_with_stride(width, height, 12, bytes).unwrap();
+        let crop_box = CropBox {
+            left: 1.,
+            top: 1.,
+            width: 2.,
+            height: 3.,
+        };
+        let mut cropped = view.try_crop(crop_box).unwrap();
+        for (y, row) in cropped.iter_rows_mut().enumerate() {
+            for (x, pixel) in row.iter_mut().enumerate() {
+                *pixel = U16::new((y * 10 + x + 1) as u16);
+            }
+        }
+
+        let cropped: ImageView<U16> = cropped.into();
+        let rows: Vec<Vec<u16>> = cropped
+            .iter_rows(1)
+            .map(|row| row.iter().map(|p| p.0).collect())
+            .collect();
+        assert_eq!(rows, [[11, 12], [21, 22]]);
+        let [r0, r1] = cropped.iter_2_rows(0, 3).next().unwrap();
+        assert_eq!((r0[1].0, r1[0].0), (2, 11));
+        assert_eq!(cropped.rows_view(2..3).get_row(0).unwrap()[1].0, 22);
+
+        #[rustfmt::skip]
+        assert_eq!(
+            buffer,
+            [
+                0, 0, 0, 0, 0, 0,
+                0, 1, 2, 0, 0, 0,
+                0, 11, 12, 0, 0, 0,
+                0, 21, 22, 0, 0, 0,
+                0, 0, 0, 0,
+            ]
+        );
+    }
+
+    #[cfg(feature = "checked")]
+    #[test]
+    #[should_panic(expected = "is smaller than required for 4x3 image")]
+    fn test_checked_feature_panics() {
+        let width = NonZeroU32::new(4).unwrap();
+        let height = NonZeroU32::new(3).unwrap();
+        let pixels = [U16::new(0); 11];
+        unsafe { ImageView::<U16>::from_pixels(width, height, &pixels) };
+    }
+}
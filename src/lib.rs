@@ -1,17 +1,42 @@
 #![doc = include_str!("../README.md")]
 
-pub use convolution::{FilterType, Convolution};
-pub use image_view::{change_type_of_pixel_components, CropBox, ImageView, ImageViewMut};
+pub use color::mappers::*;
+pub use color::{MappableComponent, PixelComponentMapper};
+pub use convolution::{
+    Convolution, EdgeMode, Filter, FilterType, FloatAccumulation, NoData, NoDataConvolution,
+    PixelAlignment,
+};
+pub use dynamic_image::DynamicImage;
+pub use dynamic_image_view::{DynamicImageView, DynamicImageViewMut};
+pub use errors::Error;
+pub use image_view::{
+    change_type_of_pixel_components, try_change_type_of_pixel_components, CropBox, ImageView,
+    ImageViewMut,
+};
 pub use mul_div::MulDiv;
 pub use pixels::*;
-pub use resizer::{CpuExtensions, ResizeAlg, Resizer};
+pub use plan::{ResizeJob, ResizePlan};
+pub use resizer::{AxisAlg, CpuExtensions, DstWindow, ResizeAlg, Resizer};
 pub use alpha::AlphaMulDiv;
 
 pub use crate::image::Image;
 
+/// Marks a violated precondition.
+///
+/// By default, it is `unreachable_unchecked`, so violation of the precondition
+/// is undefined behaviour. With enabled `checked` feature it panics with
+/// the given message.
+#[cfg(not(feature = "checked"))]
 macro_rules! error {
-    () => {
+    ($msg:literal $(, $arg:expr)* $(,)?) => {{
+        $(let _ = &$arg;)*
         unsafe { std::hint::unreachable_unchecked() }
+    }};
+}
+#[cfg(feature = "checked")]
+macro_rules! error {
+    ($msg:literal $(, $arg:expr)* $(,)?) => {
+        panic!($msg $(, $arg)*)
     };
 }
 use error;
@@ -20,13 +45,18 @@ use error;
 mod utils;
 
 mod alpha;
+mod color;
 mod convolution;
+mod dynamic_image;
+mod dynamic_image_view;
+mod errors;
 mod image;
 mod image_view;
 mod mul_div;
 #[cfg(target_arch = "aarch64")]
 mod neon_utils;
 pub mod pixels;
+mod plan;
 mod resizer;
 #[cfg(target_arch = "x86_64")]
 mod simd_utils;
@@ -1,9 +1,9 @@
 use crate::alpha::AlphaMulDiv;
-use crate::{error, CpuExtensions};
+use crate::{error, CpuExtensions, Error};
 use crate::{ImageView, ImageViewMut};
 
 /// Methods of this structure used to multiply or divide color-channels (RGB or Luma)
-/// by alpha-channel. Supported pixel types: U8x2, U8x4, U16x2 and U16x4.
+/// by alpha-channel. Supported pixel types: U8x2, U8x4, U16x2, U16x4, F32x2 and F32x4.
 ///
 /// By default, instance of `MulDiv` created with best CPU-extensions provided by your CPU.
 /// You can change this by use method [MulDiv::set_cpu_extensions].
@@ -50,11 +50,30 @@ impl MulDiv {
     ) {
         let cpu_extensions = self.cpu_extensions;
         if src_image.width() != dst_image.width() || src_image.height() != dst_image.height() {
-            error!();
+            error!(
+                "Size of source image ({}x{}) is not equal to size of destination image ({}x{})",
+                src_image.width(),
+                src_image.height(),
+                dst_image.width(),
+                dst_image.height()
+            );
         }
         P::multiply_alpha(src_image, dst_image, cpu_extensions);
     }
 
+    /// Checked version of [`MulDiv::multiply_alpha`].
+    pub fn try_multiply_alpha<P: AlphaMulDiv>(
+        &self,
+        src_image: &ImageView<'_, P>,
+        dst_image: &mut ImageViewMut<'_, P>,
+    ) -> Result<(), Error> {
+        if src_image.width() != dst_image.width() || src_image.height() != dst_image.height() {
+            return Err(Error::SizeMismatch);
+        }
+        P::multiply_alpha(src_image, dst_image, self.cpu_extensions);
+        Ok(())
+    }
+
     /// Multiplies color-channels (RGB or Luma) of image by alpha-channel inplace.
     pub fn multiply_alpha_inplace<P: AlphaMulDiv>(&self, image: &mut ImageViewMut<'_, P>) {
         let cpu_extensions = self.cpu_extensions;
@@ -70,11 +89,30 @@ impl MulDiv {
     ) {
         let cpu_extensions = self.cpu_extensions;
         if src_image.width() != dst_image.width() || src_image.height() != dst_image.height() {
-            error!();
+            error!(
+                "Size of source image ({}x{}) is not equal to size of destination image ({}x{})",
+                src_image.width(),
+                src_image.height(),
+                dst_image.width(),
+                dst_image.height()
+            );
         }
         P::divide_alpha(src_image, dst_image, cpu_extensions);
     }
 
+    /// Checked version of [`MulDiv::divide_alpha`].
+    pub fn try_divide_alpha<P: AlphaMulDiv>(
+        &self,
+        src_image: &ImageView<'_, P>,
+        dst_image: &mut ImageViewMut<'_, P>,
+    ) -> Result<(), Error> {
+        if src_image.width() != dst_image.width() || src_image.height() != dst_image.height() {
+            return Err(Error::SizeMismatch);
+        }
+        P::divide_alpha(src_image, dst_image, self.cpu_extensions);
+        Ok(())
+    }
+
     /// Divides color-channels (RGB or Luma) of image by alpha-channel inplace.
     pub fn divide_alpha_inplace<P: AlphaMulDiv>(&self, image: &mut ImageViewMut<'_, P>) {
         let cpu_extensions = self.cpu_extensions;
@@ -1,7 +1,7 @@
 //! Contains types of pixels.
 use std::fmt::{Debug, Formatter};
 use std::marker::PhantomData;
-use std::mem::size_of;
+use std::mem::{align_of, size_of};
 use std::slice;
 pub trait GetCount {
     fn count() -> usize;
@@ -188,6 +188,87 @@ pixel_struct!(
 );
 pixel_struct!(I32, i32, i32, 1, "One `i32` component per pixel");
 pixel_struct!(F32, f32, f32, 1, "One `f32` component per pixel");
+pixel_struct!(
+    F32x2,
+    [f32; 2],
+    f32,
+    2,
+    "Two `f32` components per pixel (e.g. LA32F)"
+);
+pixel_struct!(
+    F32x3,
+    [f32; 3],
+    f32,
+    3,
+    "Three `f32` components per pixel (e.g. RGB32F)"
+);
+pixel_struct!(
+    F32x4,
+    [f32; 4],
+    f32,
+    4,
+    "Four `f32` components per pixel (e.g. RGBA32F)"
+);
+
+/// Type of pixels of an image that is known only at runtime.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PixelType {
+    U8,
+    U8x2,
+    U8x3,
+    U8x4,
+    U16,
+    U16x2,
+    U16x3,
+    U16x4,
+    I32,
+    F32,
+    F32x2,
+    F32x3,
+    F32x4,
+}
+
+impl PixelType {
+    /// Returns pixel size in bytes.
+    pub fn size(&self) -> usize {
+        match self {
+            Self::U8 => U8::size(),
+            Self::U8x2 => U8x2::size(),
+            Self::U8x3 => U8x3::size(),
+            Self::U8x4 => U8x4::size(),
+            Self::U16 => U16::size(),
+            Self::U16x2 => U16x2::size(),
+            Self::U16x3 => U16x3::size(),
+            Self::U16x4 => U16x4::size(),
+            Self::I32 => I32::size(),
+            Self::F32 => F32::size(),
+            Self::F32x2 => F32x2::size(),
+            Self::F32x3 => F32x3::size(),
+            Self::F32x4 => F32x4::size(),
+        }
+    }
+
+    /// Returns `true` if given buffer is aligned by the alignment of pixel.
+    pub fn is_aligned(&self, buffer: &[u8]) -> bool {
+        let align = match self {
+            Self::U8 => align_of::<U8>(),
+            Self::U8x2 => align_of::<U8x2>(),
+            Self::U8x3 => align_of::<U8x3>(),
+            Self::U8x4 => align_of::<U8x4>(),
+            Self::U16 => align_of::<U16>(),
+            Self::U16x2 => align_of::<U16x2>(),
+            Self::U16x3 => align_of::<U16x3>(),
+            Self::U16x4 => align_of::<U16x4>(),
+            Self::I32 => align_of::<I32>(),
+            Self::F32 => align_of::<F32>(),
+            Self::F32x2 => align_of::<F32x2>(),
+            Self::F32x3 => align_of::<F32x3>(),
+            Self::F32x4 => align_of::<F32x4>(),
+        };
+        buffer.as_ptr().align_offset(align) == 0
+    }
+}
 
 pub trait IntoPixelComponent<Out: PixelComponent>
 where
@@ -0,0 +1,411 @@
+use std::num::{NonZeroU32, NonZeroUsize};
+
+use crate::convolution::{Convolution, PixelAlignment};
+use crate::resizer::{self, ConvolutionBuffers, ConvolutionOptions, ConvolutionPasses};
+use crate::{
+    AxisAlg, CpuExtensions, CropBox, DstWindow, Error, ImageView, ImageViewMut, ResizeAlg, Resizer,
+};
+
+/// Precomputed plan of resizing that can be split into independent jobs.
+///
+/// Each job calculates its own band of rows of destination image and
+/// can be executed by any executor (thread pool, async runtime, etc.).
+/// Results of all jobs are bit-identical to the result of
+/// [`Resizer::resize`](crate::Resizer::resize) with the same algorithm.
+///
+/// ```
+/// use std::num::{NonZeroU32, NonZeroUsize};
+/// use fer as fr;
+///
+/// let src_image = fr::Image::<fr::U8x4>::new(
+///     NonZeroU32::new(64).unwrap(),
+///     NonZeroU32::new(64).unwrap(),
+/// );
+/// let mut dst_image = fr::Image::<fr::U8x4>::new(
+///     NonZeroU32::new(16).unwrap(),
+///     NonZeroU32::new(16).unwrap(),
+/// );
+/// let src_view = src_image.try_view().unwrap();
+/// let mut dst_view = dst_image.try_view_mut().unwrap();
+///
+/// let resizer = fr::Resizer::new(fr::ResizeAlg::Convolution(fr::FilterType::Lanczos3));
+/// let plan = resizer.plan(&src_view, &dst_view);
+/// let jobs = plan
+///     .split(&src_view, &mut dst_view, NonZeroUsize::new(4).unwrap())
+///     .unwrap();
+/// std::thread::scope(|scope| {
+///     for job in jobs {
+///         scope.spawn(move || job.run());
+///     }
+/// });
+/// ```
+#[derive(Debug, Clone)]
+pub struct ResizePlan {
+    src_width: NonZeroU32,
+    src_height: NonZeroU32,
+    crop_box: CropBox,
+    dst_width: NonZeroU32,
+    dst_height: NonZeroU32,
+    cpu_extensions: CpuExtensions,
+    pixel_alignment: PixelAlignment,
+    kind: PlanKind,
+}
+
+#[derive(Debug, Clone)]
+enum PlanKind {
+    /// Sizes of source and destination images are equal.
+    Nothing,
+    Nearest,
+    Convolution(ConvolutionPasses),
+    SuperSampling {
+        tmp_width: NonZeroU32,
+        tmp_height: NonZeroU32,
+        passes: ConvolutionPasses,
+    },
+}
+
+impl ResizePlan {
+    pub(crate) fn new(
+        resizer: &Resizer,
+        src_width: NonZeroU32,
+        src_height: NonZeroU32,
+        crop_box: CropBox,
+        dst_width: NonZeroU32,
+        dst_height: NonZeroU32,
+    ) -> Self {
+        let float_accumulation = resizer.float_accumulation();
+        let convolution_passes = |horizontal: AxisAlg, vertical: AxisAlg| {
+            ConvolutionPasses::new(
+                src_width,
+                src_height,
+                crop_box,
+                dst_width,
+                dst_height,
+                resizer.convolution_params(horizontal, vertical),
+            )
+            .with_float_accumulation(float_accumulation)
+        };
+        let kind = if !resizer::need_resize(crop_box, dst_width, dst_height) {
+            PlanKind::Nothing
+        } else {
+            match resizer.algorithm.clone() {
+                ResizeAlg::Nearest => PlanKind::Nearest,
+                ResizeAlg::Convolution(filter_type) => PlanKind::Convolution(convolution_passes(
+                    filter_type.clone().into(),
+                    filter_type.into(),
+                )),
+                ResizeAlg::SuperSampling(filter_type, multiplicity) => {
+                    match resizer::super_sampling_size(
+                        crop_box,
+                        dst_width,
+                        dst_height,
+                        multiplicity,
+                    ) {
+                        Some((tmp_width, tmp_height)) => PlanKind::SuperSampling {
+                            tmp_width,
+                            tmp_height,
+                            passes: ConvolutionPasses::new(
+                                tmp_width,
+                                tmp_height,
+                                CropBox::full(tmp_width, tmp_height),
+                                dst_width,
+                                dst_height,
+                                resizer.convolution_params(
+                                    filter_type.clone().into(),
+                                    filter_type.into(),
+                                ),
+                            )
+                            .with_float_accumulation(float_accumulation),
+                        },
+                        None => PlanKind::Convolution(convolution_passes(
+                            filter_type.clone().into(),
+                            filter_type.into(),
+                        )),
+                    }
+                }
+                ResizeAlg::Separable {
+                    horizontal: AxisAlg::Nearest,
+                    vertical: AxisAlg::Nearest,
+                } => PlanKind::Nearest,
+                ResizeAlg::Separable {
+                    horizontal,
+                    vertical,
+                } => PlanKind::Convolution(convolution_passes(horizontal, vertical)),
+            }
+        };
+        Self {
+            src_width,
+            src_height,
+            crop_box,
+            dst_width,
+            dst_height,
+            cpu_extensions: resizer.cpu_extensions(),
+            pixel_alignment: resizer.pixel_alignment(),
+            kind,
+        }
+    }
+
+    /// Splits the plan into at most `count` jobs. Each job owns
+    /// a band of rows of the destination image.
+    ///
+    /// Returns [`Error::SizeMismatch`] if sizes of images or crop box
+    /// of the source image differ from the ones used to create the plan.
+    pub fn split<'a, P>(
+        &self,
+        src_image: &'a ImageView<'a, P>,
+        dst_image: &'a mut ImageViewMut<'_, P>,
+        count: NonZeroUsize,
+    ) -> Result<Vec<ResizeJob<'a, P>>, Error>
+    where
+        P: Convolution,
+    {
+        if src_image.width() != self.src_width
+            || src_image.height() != self.src_height
+            || src_image.crop_box() != self.crop_box
+            || dst_image.width() != self.dst_width
+            || dst_image.height() != self.dst_height
+        {
+            return Err(Error::SizeMismatch);
+        }
+        match &self.kind {
+            PlanKind::Convolution(passes) | PlanKind::SuperSampling { passes, .. } => {
+                // Normalized coefficients are shared between the plan and its jobs
+                passes.normalize_for::<P>();
+            }
+            _ => (),
+        }
+        let band_height =
+            resizer::get_stripe_height(self.dst_height, count).unwrap_or(self.dst_height);
+        let mut first_row = 0;
+        let jobs = dst_image
+            .split_by_height(band_height)
+            .into_iter()
+            .map(|band| {
+                let rows = first_row..first_row + band.height().get();
+                first_row = rows.end;
+                let kind = match &self.kind {
+                    PlanKind::Convolution(passes) => {
+                        PlanKind::Convolution(passes.slice(rows.clone()))
+                    }
+                    PlanKind::SuperSampling {
+                        tmp_width,
+                        tmp_height,
+                        passes,
+                    } => PlanKind::SuperSampling {
+                        tmp_width: *tmp_width,
+                        tmp_height: *tmp_height,
+                        passes: passes.slice(rows.clone()),
+                    },
+                    kind => kind.clone(),
+                };
+                ResizeJob {
+                    src_image,
+                    dst_image: band,
+                    first_dst_row: rows.start,
+                    dst_height: self.dst_height,
+                    cpu_extensions: self.cpu_extensions,
+                    pixel_alignment: self.pixel_alignment,
+                    kind,
+                }
+            })
+            .collect();
+        Ok(jobs)
+    }
+}
+
+/// Independent part of [`ResizePlan`] which calculates a band of rows
+/// of the destination image.
+#[derive(Debug)]
+pub struct ResizeJob<'a, P>
+where
+    P: Convolution,
+{
+    src_image: &'a ImageView<'a, P>,
+    dst_image: ImageViewMut<'a, P>,
+    first_dst_row: u32,
+    dst_height: NonZeroU32,
+    cpu_extensions: CpuExtensions,
+    pixel_alignment: PixelAlignment,
+    kind: PlanKind,
+}
+
+impl<'a, P> ResizeJob<'a, P>
+where
+    P: Convolution,
+{
+    /// Returns the index of the first row of destination image
+    /// calculated by this job.
+    pub fn first_dst_row(&self) -> u32 {
+        self.first_dst_row
+    }
+
+    /// Returns the number of rows of destination image
+    /// calculated by this job.
+    pub fn dst_rows_count(&self) -> NonZeroU32 {
+        self.dst_image.height()
+    }
+
+    /// Calculates the band of rows of destination image.
+    pub fn run(mut self) {
+        let options = ConvolutionOptions::new(self.cpu_extensions, NonZeroUsize::MIN);
+        let mut buffers = ConvolutionBuffers::default();
+        let window = DstWindow {
+            top: self.first_dst_row,
+            ..DstWindow::full(self.dst_image.width(), self.dst_height)
+        };
+        match self.kind {
+            PlanKind::Nothing => {}
+            PlanKind::Nearest => resizer::resample_nearest(
+                self.src_image,
+                &mut self.dst_image,
+                window,
+                self.pixel_alignment,
+            ),
+            PlanKind::Convolution(passes) => resizer::convolve(
+                self.src_image,
+                &mut self.dst_image,
+                window,
+                passes,
+                options,
+                &mut buffers,
+            ),
+            PlanKind::SuperSampling {
+                tmp_width,
+                tmp_height,
+                mut passes,
+            } => {
+                // Only the rows of temporary image required by
+                // the second step are resized by the nearest algorithm.
+                // Wrapped rows may be taken from any part of the image.
+                let tmp_crop_box = CropBox::full(tmp_width, tmp_height);
+                let dst_rows =
+                    self.first_dst_row..self.first_dst_row + self.dst_image.height().get();
+                let tmp_rows = match passes.wrap_padding {
+                    Some(_) => 0..tmp_height.get(),
+                    None => passes.src_rows(tmp_crop_box, dst_rows),
+                };
+                let tmp_rows_count = NonZeroU32::new(tmp_rows.end - tmp_rows.start).unwrap();
+                let mut tmp_buffer = Vec::new();
+                let mut tmp_img =
+                    resizer::get_temp_image_from_buffer(&mut tmp_buffer, tmp_width, tmp_rows_count);
+                let tmp_window = DstWindow {
+                    top: tmp_rows.start,
+                    ..DstWindow::full(tmp_width, tmp_height)
+                };
+                resizer::resample_nearest(
+                    self.src_image,
+                    unsafe { &mut tmp_img.dst_view() },
+                    tmp_window,
+                    self.pixel_alignment,
+                );
+                passes.skip_src_rows(tmp_rows.start);
+                // Without vertical pass the rows of temporary image
+                // match the rows of destination image.
+                let window = DstWindow {
+                    top: window.top.saturating_sub(tmp_rows.start),
+                    ..window
+                };
+                resizer::convolve(
+                    unsafe { &tmp_img.src_view() },
+                    &mut self.dst_image,
+                    window,
+                    passes,
+                    options,
+                    &mut buffers,
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pixels::{U8x4, F32};
+    use crate::{FilterType, Image};
+
+    fn resize_by_jobs<P: Convolution>(
+        algorithm: ResizeAlg,
+        src_image: &ImageView<P>,
+        dst_size: (u32, u32),
+        expected_kind: Option<fn(&PlanKind) -> bool>,
+    ) {
+        let dst_width = NonZeroU32::new(dst_size.0).unwrap();
+        let dst_height = NonZeroU32::new(dst_size.1).unwrap();
+        let mut resizer = Resizer::new(algorithm);
+        let mut expected = Image::<P>::new(dst_width, dst_height);
+        unsafe { resizer.resize(src_image, &mut expected.try_view_mut().unwrap()) };
+
+        for count in [1, 2, 5, 100] {
+            let mut result = Image::<P>::new(dst_width, dst_height);
+            let mut dst_view = result.try_view_mut().unwrap();
+            let plan = resizer.plan(src_image, &dst_view);
+            if let Some(expected_kind) = expected_kind {
+                assert!(expected_kind(&plan.kind));
+            }
+            let count = NonZeroUsize::new(count).unwrap();
+            let jobs = plan.split(src_image, &mut dst_view, count).unwrap();
+            std::thread::scope(|scope| {
+                for job in jobs {
+                    scope.spawn(move || job.run());
+                }
+            });
+            assert_eq!(
+                result.buffer(),
+                expected.buffer(),
+                "{:?} {count}",
+                resizer.algorithm
+            );
+        }
+    }
+
+    #[test]
+    fn test_jobs_of_plan() {
+        let src_width = NonZeroU32::new(67).unwrap();
+        let src_height = NonZeroU32::new(45).unwrap();
+        let buffer: Vec<u8> = (0..src_width.get() * src_height.get() * 4)
+            .map(|i| (i * 37 % 251) as u8)
+            .collect();
+        let src_u8x4 = Image::<U8x4>::try_from_vec_u8(src_width, src_height, buffer).unwrap();
+        let buffer: Vec<u8> = (0..src_width.get() * src_height.get())
+            .flat_map(|i| (i as f32 * 0.37 % 25.1).to_ne_bytes())
+            .collect();
+        let src_f32 = Image::<F32>::try_from_vec_u8(src_width, src_height, buffer).unwrap();
+        let mut cropped_f32 = src_f32.try_view().unwrap();
+        cropped_f32
+            .try_set_crop_box(CropBox {
+                left: 3.5,
+                top: 2.,
+                width: 50.,
+                height: 30.25,
+            })
+            .unwrap();
+
+        let is_convolution: fn(&PlanKind) -> bool = |k| matches!(k, PlanKind::Convolution(_));
+        let is_super_sampling: fn(&PlanKind) -> bool =
+            |k| matches!(k, PlanKind::SuperSampling { .. });
+        let is_nearest: fn(&PlanKind) -> bool = |k| matches!(k, PlanKind::Nearest);
+        let cases = [
+            (ResizeAlg::Convolution(FilterType::Lanczos3), is_convolution),
+            (
+                ResizeAlg::SuperSampling(FilterType::Bilinear, 2),
+                is_super_sampling,
+            ),
+            (ResizeAlg::Nearest, is_nearest),
+        ];
+        for (algorithm, expected_kind) in cases {
+            // Only large enough downscaling uses all kinds of plans
+            let sizes = [
+                ((11, 7), Some(expected_kind)),
+                ((13, 45), None),
+                ((67, 7), None),
+                ((90, 60), None),
+            ];
+            for (dst_size, expected_kind) in sizes {
+                let src_view = src_u8x4.try_view().unwrap();
+                resize_by_jobs(algorithm.clone(), &src_view, dst_size, expected_kind);
+                resize_by_jobs(algorithm.clone(), &cropped_f32, dst_size, expected_kind);
+            }
+        }
+    }
+}
@@ -1,9 +1,18 @@
-use std::num::NonZeroU32;
+use std::num::{NonZeroU32, NonZeroUsize};
+use std::ops::Range;
+use std::thread;
 
-use crate::convolution::{self, Convolution, FilterType};
+use crate::alpha::AlphaMulDiv;
+use crate::convolution::{
+    self, Coefficients, Convolution, EdgeMode, FilterType, FloatAccumulation, NoData,
+    NoDataConvolution, PixelAlignment,
+};
 use crate::image::InnerImage;
+use crate::image_view;
 use crate::pixels::PixelExt;
-use crate::{ImageView, ImageViewMut};
+use crate::{
+    CropBox, DynamicImageView, DynamicImageViewMut, Error, ImageView, ImageViewMut, ResizePlan,
+};
 
 /// SIMD extension of CPU.
 /// Specific variants depends from target architecture.
@@ -78,12 +87,20 @@ impl Default for CpuExtensions {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+/// Algorithm of resizing. It is not [`Copy`] because [`FilterType`]
+/// is not, use [`Clone`] instead.
+#[derive(Debug, Clone)]
 #[non_exhaustive]
 pub enum ResizeAlg {
     Nearest,
     Convolution(FilterType),
     SuperSampling(FilterType, u8),
+    /// Independent algorithms of resizing along horizontal
+    /// and vertical axes of image.
+    Separable {
+        horizontal: AxisAlg,
+        vertical: AxisAlg,
+    },
 }
 
 impl Default for ResizeAlg {
@@ -92,13 +109,77 @@ impl Default for ResizeAlg {
     }
 }
 
+/// Algorithm of resizing along one axis of image.
+/// See [`ResizeAlg::Separable`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AxisAlg {
+    Nearest,
+    Convolution(FilterType),
+}
+
+impl From<FilterType> for AxisAlg {
+    fn from(filter_type: FilterType) -> Self {
+        Self::Convolution(filter_type)
+    }
+}
+
+/// Position of destination image inside of a larger virtual
+/// destination image.
+///
+/// Coefficients of convolution are calculated for the whole virtual
+/// image, so any window of it can be resized independently and adjacent
+/// windows are joined seamlessly. See [`Resizer::resize_window`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DstWindow {
+    /// Width of the virtual destination image.
+    pub width: NonZeroU32,
+    /// Height of the virtual destination image.
+    pub height: NonZeroU32,
+    /// Column of the virtual image where the destination image starts.
+    pub left: u32,
+    /// Row of the virtual image where the destination image starts.
+    pub top: u32,
+}
+
+impl DstWindow {
+    /// Returns window which covers the whole destination image
+    /// with given size.
+    pub(crate) fn full(width: NonZeroU32, height: NonZeroU32) -> Self {
+        Self {
+            width,
+            height,
+            left: 0,
+            top: 0,
+        }
+    }
+
+    /// Returns columns of the virtual image covered by destination
+    /// image with given width.
+    fn cols(&self, dst_width: NonZeroU32) -> Range<u32> {
+        self.left..self.left + dst_width.get()
+    }
+
+    /// Returns rows of the virtual image covered by destination
+    /// image with given height.
+    fn rows(&self, dst_height: NonZeroU32) -> Range<u32> {
+        self.top..self.top + dst_height.get()
+    }
+}
+
 /// Methods of this structure used to resize images.
 #[derive(Default, Debug, Clone)]
 pub struct Resizer {
     pub algorithm: ResizeAlg,
     cpu_extensions: CpuExtensions,
-    convolution_buffer: Vec<u8>,
+    threads: Option<NonZeroUsize>,
+    float_accumulation: FloatAccumulation,
+    blur: Option<f64>,
+    pixel_alignment: PixelAlignment,
+    edge_mode: EdgeMode,
+    convolution_buffers: ConvolutionBuffers,
     super_sampling_buffer: Vec<u8>,
+    coefficients_cache: CoefficientsCache,
 }
 
 impl Resizer {
@@ -118,57 +199,269 @@ impl Resizer {
     ///
     /// This method doesn't multiply source image and doesn't divide
     /// destination image by alpha channel.
-    /// You must use [MulDiv](crate::MulDiv) for these actions.
+    /// You must use [MulDiv](crate::MulDiv) for these actions or
+    /// use [`Resizer::resize_with_alpha`] instead.
     pub unsafe fn resize<P>(&mut self, src_image: &ImageView<P>, dst_image: &mut ImageViewMut<P>)
     where
         P: Convolution,
     {
-        if {
-            let src_crop_box = src_image.crop_box();
-            dst_image.width == src_crop_box.width && dst_image.height == src_crop_box.height
-        } {
+        let options = ConvolutionOptions::new(self.cpu_extensions, self.threads());
+        let window = DstWindow::full(dst_image.width(), dst_image.height());
+        self.resize_with_options(src_image, dst_image, window, options);
+    }
+
+    /// Resize source image into the window of a larger virtual
+    /// destination image and save the result to the pixel buffer of
+    /// destination image, which has the size of the window.
+    ///
+    /// The result is equal to the corresponding region of the virtual
+    /// image resized by [`Resizer::resize`].
+    ///
+    /// # Safety
+    /// Destination image must be inside of the virtual image.
+    /// Otherwise, the same as for [`Resizer::resize`].
+    pub unsafe fn resize_window<P>(
+        &mut self,
+        src_image: &ImageView<P>,
+        dst_image: &mut ImageViewMut<P>,
+        window: DstWindow,
+    ) where
+        P: Convolution,
+    {
+        if check_dst_window(window, dst_image.width(), dst_image.height()).is_err() {
+            error!(
+                "{}x{} destination image is out of the boundaries of {:?}",
+                dst_image.width(),
+                dst_image.height(),
+                window
+            );
+        }
+        let options = ConvolutionOptions::new(self.cpu_extensions, self.threads());
+        self.resize_with_options(src_image, dst_image, window, options);
+    }
+
+    /// Checked version of [`Resizer::resize_window`].
+    pub fn try_resize_window<P>(
+        &mut self,
+        src_image: &ImageView<P>,
+        dst_image: &mut ImageViewMut<P>,
+        window: DstWindow,
+    ) -> Result<(), Error>
+    where
+        P: Convolution,
+    {
+        check_dst_window(window, dst_image.width(), dst_image.height())?;
+        unsafe { self.resize_window(src_image, dst_image, window) };
+        Ok(())
+    }
+
+    /// Resize source image to fit inside of destination image without
+    /// distortions and cropping, and fill the rest of destination image
+    /// with `background` pixel.
+    ///
+    /// The aspect ratio of the crop box of source image is preserved.
+    /// `centering` used to control the position of resized image, it
+    /// has the same meaning as in [`ImageView::set_crop_box_to_fit_dst_size`].
+    /// Use (0.5, 0.5) to place the image at the center of destination image.
+    ///
+    /// # Safety
+    /// The same as for [`Resizer::resize`].
+    pub unsafe fn resize_to_fit<P>(
+        &mut self,
+        src_image: &ImageView<P>,
+        dst_image: &mut ImageViewMut<P>,
+        centering: Option<(f32, f32)>,
+        background: P,
+    ) where
+        P: Convolution,
+    {
+        check_crop_box(src_image);
+        let crop_box = src_image.crop_box();
+        let region = fit_region(crop_box, dst_image.width(), dst_image.height(), centering);
+        fill_around_region(dst_image, region, background);
+        let mut region_view = unsafe { dst_image.reborrow().crop(region) };
+        if need_resize(crop_box, region_view.width(), region_view.height()) {
+            unsafe { self.resize(src_image, &mut region_view) };
+        } else {
+            // Sizes are equal, so pixels are copied without resizing
+            let src_view = unsafe { src_image.sub_view(crop_box) };
+            let rows = src_view.iter_rows(0).zip(region_view.iter_rows_mut());
+            rows.for_each(|(src_row, dst_row)| dst_row.copy_from_slice(src_row));
+        }
+    }
+
+    /// Checked version of [`Resizer::resize_to_fit`].
+    ///
+    /// Returns [`Error::CropBoxOutOfBounds`] if the crop box of source
+    /// image is out of its boundaries.
+    pub fn try_resize_to_fit<P>(
+        &mut self,
+        src_image: &ImageView<P>,
+        dst_image: &mut ImageViewMut<P>,
+        centering: Option<(f32, f32)>,
+        background: P,
+    ) -> Result<(), Error>
+    where
+        P: Convolution,
+    {
+        image_view::check_crop_box(src_image.width(), src_image.height(), src_image.crop_box())?;
+        unsafe { self.resize_to_fit(src_image, dst_image, centering, background) };
+        Ok(())
+    }
+
+    /// Resize source image with alpha channel to the size of destination
+    /// image and save the result to the latter's pixel buffer.
+    ///
+    /// Color-channels of source image are multiplied by alpha channel
+    /// on the fly before convolution, and color-channels of destination
+    /// image are divided by alpha channel right after it. So there is no
+    /// need to use [MulDiv](crate::MulDiv) and additional full-size
+    /// images for these actions.
+    ///
+    /// # Safety
+    /// The same as for [`Resizer::resize`].
+    pub unsafe fn resize_with_alpha<P>(
+        &mut self,
+        src_image: &ImageView<P>,
+        dst_image: &mut ImageViewMut<P>,
+    ) where
+        P: Convolution + AlphaMulDiv,
+    {
+        let options = ConvolutionOptions::new(self.cpu_extensions, self.threads()).with_alpha();
+        let window = DstWindow::full(dst_image.width(), dst_image.height());
+        self.resize_with_options(src_image, dst_image, window, options);
+    }
+
+    /// Resize source image with "no data" components to the size of
+    /// destination image and save the result to the latter's pixel buffer.
+    ///
+    /// Components equal to `nodata` are excluded from the convolution,
+    /// and coefficients of the remaining components are renormalized
+    /// for every pixel of destination image. The "no data" value is
+    /// written into destination image only if no valid component
+    /// contributes into the result. See [`NoDataConvolution`].
+    ///
+    /// This kind of convolution doesn't use SIMD-extensions of CPU
+    /// and always accumulates values in `f64`.
+    ///
+    /// # Safety
+    /// The same as for [`Resizer::resize`].
+    pub unsafe fn resize_with_nodata<P>(
+        &mut self,
+        src_image: &ImageView<P>,
+        dst_image: &mut ImageViewMut<P>,
+        nodata: NoData,
+    ) where
+        P: NoDataConvolution,
+    {
+        let options =
+            ConvolutionOptions::new(self.cpu_extensions, self.threads()).with_nodata(nodata);
+        let window = DstWindow::full(dst_image.width(), dst_image.height());
+        self.resize_with_options(src_image, dst_image, window, options);
+    }
+
+    fn resize_with_options<P>(
+        &mut self,
+        src_image: &ImageView<P>,
+        dst_image: &mut ImageViewMut<P>,
+        window: DstWindow,
+        options: ConvolutionOptions<P>,
+    ) where
+        P: Convolution,
+    {
+        check_crop_box(src_image);
+        if !need_resize(src_image.crop_box(), window.width, window.height) {
             return;
         }
-        match self.algorithm {
-            ResizeAlg::Nearest => resample_nearest(src_image, dst_image),
+        match self.algorithm.clone() {
+            // Nearest algorithm only copies pixels, so it doesn't
+            // need multiplication by alpha channel.
+            ResizeAlg::Nearest => {
+                resample_nearest(src_image, dst_image, window, self.pixel_alignment)
+            }
             ResizeAlg::Convolution(filter_type) => {
-                let convolution_buffer = &mut self.convolution_buffer;
-                resample_convolution(
-                    src_image,
-                    dst_image,
-                    filter_type,
-                    self.cpu_extensions,
-                    convolution_buffer,
-                )
+                let params =
+                    self.convolution_params(filter_type.clone().into(), filter_type.into());
+                self.resample_convolution(src_image, dst_image, window, params, options)
             }
-            ResizeAlg::SuperSampling(filter_type, multiplicity) => {
-                let convolution_buffer = &mut self.convolution_buffer;
-                let super_sampling_buffer = &mut self.super_sampling_buffer;
-                resample_super_sampling(
-                    src_image,
-                    dst_image,
-                    filter_type,
-                    multiplicity,
-                    self.cpu_extensions,
-                    super_sampling_buffer,
-                    convolution_buffer,
-                )
+            ResizeAlg::SuperSampling(filter_type, multiplicity) => self.resample_super_sampling(
+                src_image,
+                dst_image,
+                window,
+                filter_type,
+                multiplicity,
+                options,
+            ),
+            ResizeAlg::Separable {
+                horizontal: AxisAlg::Nearest,
+                vertical: AxisAlg::Nearest,
+            } => resample_nearest(src_image, dst_image, window, self.pixel_alignment),
+            ResizeAlg::Separable {
+                horizontal,
+                vertical,
+            } => {
+                let params = self.convolution_params(horizontal, vertical);
+                self.resample_convolution(src_image, dst_image, window, params, options)
+            }
+        }
+    }
+
+    /// Resize source image with type of pixels known only at runtime
+    /// to the size of destination image.
+    ///
+    /// Returns [`Error::DifferentTypesOfPixels`] if source and destination
+    /// images have different types of pixels.
+    ///
+    /// # Safety
+    /// The same as for [`Resizer::resize`].
+    pub unsafe fn resize_dynamic(
+        &mut self,
+        src_image: &DynamicImageView,
+        dst_image: &mut DynamicImageViewMut,
+    ) -> Result<(), Error> {
+        match (src_image, dst_image) {
+            (DynamicImageView::U8(src), DynamicImageViewMut::U8(dst)) => self.resize(src, dst),
+            (DynamicImageView::U8x2(src), DynamicImageViewMut::U8x2(dst)) => self.resize(src, dst),
+            (DynamicImageView::U8x3(src), DynamicImageViewMut::U8x3(dst)) => self.resize(src, dst),
+            (DynamicImageView::U8x4(src), DynamicImageViewMut::U8x4(dst)) => self.resize(src, dst),
+            (DynamicImageView::U16(src), DynamicImageViewMut::U16(dst)) => self.resize(src, dst),
+            (DynamicImageView::U16x2(src), DynamicImageViewMut::U16x2(dst)) => {
+                self.resize(src, dst)
+            }
+            (DynamicImageView::U16x3(src), DynamicImageViewMut::U16x3(dst)) => {
+                self.resize(src, dst)
+            }
+            (DynamicImageView::U16x4(src), DynamicImageViewMut::U16x4(dst)) => {
+                self.resize(src, dst)
             }
+            (DynamicImageView::I32(src), DynamicImageViewMut::I32(dst)) => self.resize(src, dst),
+            (DynamicImageView::F32(src), DynamicImageViewMut::F32(dst)) => self.resize(src, dst),
+            (DynamicImageView::F32x2(src), DynamicImageViewMut::F32x2(dst)) => {
+                self.resize(src, dst)
+            }
+            (DynamicImageView::F32x3(src), DynamicImageViewMut::F32x3(dst)) => {
+                self.resize(src, dst)
+            }
+            (DynamicImageView::F32x4(src), DynamicImageViewMut::F32x4(dst)) => {
+                self.resize(src, dst)
+            }
+            _ => return Err(Error::DifferentTypesOfPixels),
         }
+        Ok(())
     }
 
     /// Returns the size of internal buffers used to store the results of
     /// intermediate resizing steps.
     pub fn size_of_internal_buffers(&self) -> usize {
-        (self.convolution_buffer.capacity() + self.super_sampling_buffer.capacity())
+        (self.convolution_buffers.capacity() + self.super_sampling_buffer.capacity())
             * std::mem::size_of::<u8>()
     }
 
     /// Deallocates the internal buffers used to store the results of
     /// intermediate resizing steps.
     pub fn reset_internal_buffers(&mut self) {
-        if self.convolution_buffer.capacity() > 0 {
-            self.convolution_buffer = Vec::new();
+        if self.convolution_buffers.capacity() > 0 {
+            self.convolution_buffers = ConvolutionBuffers::default();
         }
         if self.super_sampling_buffer.capacity() > 0 {
             self.super_sampling_buffer = Vec::new();
@@ -186,11 +479,403 @@ impl Resizer {
     pub unsafe fn set_cpu_extensions(&mut self, extensions: CpuExtensions) {
         self.cpu_extensions = extensions;
     }
+
+    /// Returns the count of threads used to resize images.
+    #[inline(always)]
+    pub fn threads(&self) -> NonZeroUsize {
+        self.threads.unwrap_or(NonZeroUsize::MIN)
+    }
+
+    /// Sets the count of threads used to resize images by convolution.
+    ///
+    /// Both passes of convolution split the destination image into stripes
+    /// of rows which are processed in parallel by scoped threads.
+    /// By default, `Resizer` uses only the current thread.
+    /// You can use [`std::thread::available_parallelism`] to get
+    /// an estimate of the parallelism available for your program.
+    pub fn set_threads(&mut self, threads: NonZeroUsize) {
+        self.threads = Some(threads);
+    }
+
+    /// Returns the type used to accumulate values of pixels
+    /// with floating-point components.
+    #[inline(always)]
+    pub fn float_accumulation(&self) -> FloatAccumulation {
+        self.float_accumulation
+    }
+
+    /// Sets the type used to accumulate values of pixels with
+    /// floating-point components (`F32`, `F32x2`, `F32x3` and `F32x4`)
+    /// during convolution.
+    ///
+    /// By default, values are accumulated in `f64`. Accumulation in `f32`
+    /// is faster, but less accurate.
+    pub fn set_float_accumulation(&mut self, float_accumulation: FloatAccumulation) {
+        self.float_accumulation = float_accumulation;
+    }
+
+    /// Returns the convention of mapping coordinates of destination
+    /// pixels into coordinates of source image.
+    #[inline(always)]
+    pub fn pixel_alignment(&self) -> PixelAlignment {
+        self.pixel_alignment
+    }
+
+    /// Sets the convention of mapping coordinates of destination pixels
+    /// into coordinates of source image. It is used by both nearest
+    /// and convolution algorithms.
+    ///
+    /// By default, [`PixelAlignment::HalfPixel`] is used.
+    pub fn set_pixel_alignment(&mut self, pixel_alignment: PixelAlignment) {
+        self.pixel_alignment = pixel_alignment;
+    }
+
+    /// Returns the handling of pixels of filter window which are
+    /// out of the boundaries of source image.
+    #[inline(always)]
+    pub fn edge_mode(&self) -> EdgeMode {
+        self.edge_mode
+    }
+
+    /// Sets the handling of pixels of filter window which are out of
+    /// the boundaries of source image during convolution.
+    ///
+    /// By default, [`EdgeMode::Truncate`] is used. [`EdgeMode::Wrap`]
+    /// requires a copy of the rows of source image used by convolution.
+    pub fn set_edge_mode(&mut self, edge_mode: EdgeMode) {
+        self.edge_mode = edge_mode;
+    }
+
+    /// Returns the factor of support of convolution filter.
+    #[inline(always)]
+    pub fn blur(&self) -> f64 {
+        self.blur.unwrap_or(1.)
+    }
+
+    /// Sets the factor of support of convolution filter.
+    ///
+    /// Values greater than `1.0` blur the image and reduce aliasing,
+    /// values less than `1.0` sharpen it. By default, the factor is `1.0`.
+    ///
+    /// # Safety
+    /// The `blur` must be a finite positive value.
+    pub unsafe fn set_blur(&mut self, blur: f64) {
+        if !(blur.is_finite() && blur > 0.) {
+            error!("Blur factor ({}) is not a finite positive value", blur);
+        }
+        self.blur = Some(blur);
+    }
+
+    /// Checked version of [`Resizer::set_blur`].
+    pub fn try_set_blur(&mut self, blur: f64) -> Result<(), Error> {
+        if !(blur.is_finite() && blur > 0.) {
+            return Err(Error::InvalidBlurFactor);
+        }
+        self.blur = Some(blur);
+        Ok(())
+    }
+
+    pub(crate) fn convolution_params(
+        &self,
+        horizontal: AxisAlg,
+        vertical: AxisAlg,
+    ) -> ConvolutionParams {
+        ConvolutionParams {
+            horizontal,
+            vertical,
+            blur: self.blur(),
+            alignment: self.pixel_alignment,
+            edge_mode: self.edge_mode,
+        }
+    }
+
+    /// Calculates in advance the coefficients of convolution used to resize
+    /// the source image into destination image with given size.
+    ///
+    /// `Resizer` keeps coefficients of the last used sizes of images,
+    /// crop box and filter, so consequent calls of [`Resizer::resize`] with
+    /// the same geometry don't calculate them again. This method allows
+    /// to avoid the delay of the first call.
+    pub fn precompute_coefficients<P>(
+        &mut self,
+        src_image: &ImageView<P>,
+        dst_width: NonZeroU32,
+        dst_height: NonZeroU32,
+    ) where
+        P: Convolution,
+    {
+        let mut crop_box = src_image.crop_box();
+        if !need_resize(crop_box, dst_width, dst_height) {
+            return;
+        }
+        let (src_width, src_height, horizontal, vertical) = match self.algorithm.clone() {
+            ResizeAlg::Nearest => return,
+            ResizeAlg::Convolution(filter_type) => (
+                src_image.width(),
+                src_image.height(),
+                filter_type.clone().into(),
+                filter_type.into(),
+            ),
+            ResizeAlg::SuperSampling(filter_type, multiplicity) => {
+                let (width, height) =
+                    match super_sampling_size(crop_box, dst_width, dst_height, multiplicity) {
+                        Some((tmp_width, tmp_height)) => {
+                            crop_box = CropBox::full(tmp_width, tmp_height);
+                            (tmp_width, tmp_height)
+                        }
+                        None => (src_image.width(), src_image.height()),
+                    };
+                (
+                    width,
+                    height,
+                    filter_type.clone().into(),
+                    filter_type.into(),
+                )
+            }
+            ResizeAlg::Separable {
+                horizontal: AxisAlg::Nearest,
+                vertical: AxisAlg::Nearest,
+            } => return,
+            ResizeAlg::Separable {
+                horizontal,
+                vertical,
+            } => (src_image.width(), src_image.height(), horizontal, vertical),
+        };
+        let passes = self.coefficients_cache.passes(
+            src_width,
+            src_height,
+            crop_box,
+            dst_width,
+            dst_height,
+            self.convolution_params(horizontal, vertical),
+        );
+        passes
+            .with_float_accumulation(self.float_accumulation)
+            .normalize_for::<P>();
+    }
+
+    /// Creates a plan of resizing the source image into the destination
+    /// image, which can be split into independent jobs.
+    ///
+    /// See [`ResizePlan`].
+    pub fn plan<P>(&self, src_image: &ImageView<P>, dst_image: &ImageViewMut<P>) -> ResizePlan
+    where
+        P: PixelExt,
+    {
+        check_crop_box(src_image);
+        ResizePlan::new(
+            self,
+            src_image.width(),
+            src_image.height(),
+            src_image.crop_box(),
+            dst_image.width(),
+            dst_image.height(),
+        )
+    }
+
+    fn resample_convolution<P>(
+        &mut self,
+        src_image: &ImageView<P>,
+        dst_image: &mut ImageViewMut<P>,
+        window: DstWindow,
+        params: ConvolutionParams,
+        options: ConvolutionOptions<P>,
+    ) where
+        P: Convolution,
+    {
+        let passes = self.window_passes::<P>(
+            src_image.width(),
+            src_image.height(),
+            src_image.crop_box(),
+            dst_image,
+            window,
+            params,
+        );
+        convolve(
+            src_image,
+            dst_image,
+            window,
+            passes,
+            options,
+            &mut self.convolution_buffers,
+        );
+    }
+
+    /// Returns passes of convolution calculated for the whole virtual
+    /// destination image and sliced for the window of destination image.
+    fn window_passes<P>(
+        &mut self,
+        src_width: NonZeroU32,
+        src_height: NonZeroU32,
+        crop_box: CropBox,
+        dst_image: &ImageViewMut<P>,
+        window: DstWindow,
+        params: ConvolutionParams,
+    ) -> ConvolutionPasses
+    where
+        P: PixelExt,
+    {
+        let passes = self
+            .coefficients_cache
+            .passes(
+                src_width,
+                src_height,
+                crop_box,
+                window.width,
+                window.height,
+                params,
+            )
+            .with_float_accumulation(self.float_accumulation);
+        // Normalized coefficients are stored inside of cached ones
+        passes.normalize_for::<P>();
+        passes.window(window, dst_image.width(), dst_image.height())
+    }
+
+    fn resample_super_sampling<P>(
+        &mut self,
+        src_image: &ImageView<P>,
+        dst_image: &mut ImageViewMut<P>,
+        window: DstWindow,
+        filter_type: FilterType,
+        multiplicity: u8,
+        options: ConvolutionOptions<P>,
+    ) where
+        P: Convolution,
+    {
+        let params = self.convolution_params(filter_type.clone().into(), filter_type.into());
+        let tmp_size = super_sampling_size(
+            src_image.crop_box(),
+            window.width,
+            window.height,
+            multiplicity,
+        );
+        if let Some((tmp_width, tmp_height)) = tmp_size {
+            let tmp_crop_box = CropBox::full(tmp_width, tmp_height);
+            let mut passes = self.window_passes::<P>(
+                tmp_width,
+                tmp_height,
+                tmp_crop_box,
+                dst_image,
+                window,
+                params,
+            );
+            // First step is resizing the source image by fastest algorithm.
+            // The temporary image will be about ``multiplicity`` times larger
+            // than required. Only the rows of temporary image required by
+            // the second step are resized. Wrapped rows may be taken
+            // from any part of the image.
+            let tmp_rows = match passes.wrap_padding {
+                Some(_) => 0..tmp_height.get(),
+                None => passes.src_rows(tmp_crop_box, window.rows(dst_image.height())),
+            };
+            let tmp_rows_count = NonZeroU32::new(tmp_rows.end - tmp_rows.start).unwrap();
+            let mut temp_buffer = std::mem::take(&mut self.super_sampling_buffer);
+            let mut tmp_img =
+                get_temp_image_from_buffer(&mut temp_buffer, tmp_width, tmp_rows_count);
+            let tmp_window = DstWindow {
+                top: tmp_rows.start,
+                ..DstWindow::full(tmp_width, tmp_height)
+            };
+            resample_nearest(
+                src_image,
+                unsafe { &mut tmp_img.dst_view() },
+                tmp_window,
+                self.pixel_alignment,
+            );
+            // Second step is resizing the temporary image with a convolution.
+            passes.skip_src_rows(tmp_rows.start);
+            // Without vertical pass the rows of temporary image
+            // match the rows of destination image.
+            let window = DstWindow {
+                top: window.top.saturating_sub(tmp_rows.start),
+                ..window
+            };
+            convolve(
+                unsafe { &tmp_img.src_view() },
+                dst_image,
+                window,
+                passes,
+                options,
+                &mut self.convolution_buffers,
+            );
+            self.super_sampling_buffer = temp_buffer;
+        } else {
+            // There is no point in doing the resizing in two steps.
+            // We immediately resize the original image with a convolution.
+            self.resample_convolution(src_image, dst_image, window, params, options);
+        }
+    }
+}
+
+fn check_crop_box<P: PixelExt>(src_image: &ImageView<P>) {
+    let crop_box = src_image.crop_box();
+    if crop_box.left + crop_box.width > src_image.width().get() as f64
+        || crop_box.top + crop_box.height > src_image.height().get() as f64
+    {
+        error!(
+            "Crop box {:?} is out of the boundaries of {}x{} source image",
+            crop_box,
+            src_image.width(),
+            src_image.height()
+        );
+    }
+}
+
+/// Checks that destination image with given size is inside
+/// of the virtual destination image.
+fn check_dst_window(
+    window: DstWindow,
+    dst_width: NonZeroU32,
+    dst_height: NonZeroU32,
+) -> Result<(), Error> {
+    let right = window.left as u64 + dst_width.get() as u64;
+    let bottom = window.top as u64 + dst_height.get() as u64;
+    if right > window.width.get() as u64 || bottom > window.height.get() as u64 {
+        return Err(Error::DstWindowOutOfBounds);
+    }
+    Ok(())
+}
+
+/// Returns region of destination image with given size that contains
+/// the crop box resized without distortions.
+fn fit_region(
+    crop_box: CropBox,
+    dst_width: NonZeroU32,
+    dst_height: NonZeroU32,
+    centering: Option<(f32, f32)>,
+) -> CropBox {
+    let (x, y) = centering.unwrap_or((0.5, 0.5));
+    let centering = (x.clamp(0.0, 1.0) as f64, y.clamp(0.0, 1.0) as f64);
+    let dst_width = dst_width.get() as f64;
+    let dst_height = dst_height.get() as f64;
+    let scale = (dst_width / crop_box.width).min(dst_height / crop_box.height);
+    let width = (crop_box.width * scale).round().clamp(1., dst_width);
+    let height = (crop_box.height * scale).round().clamp(1., dst_height);
+    CropBox {
+        left: ((dst_width - width) * centering.0).round(),
+        top: ((dst_height - height) * centering.1).round(),
+        width,
+        height,
+    }
+}
+
+/// Fills pixels of the image outside of given region with integer boundaries.
+fn fill_around_region<P: PixelExt>(image: &mut ImageViewMut<P>, region: CropBox, pixel: P) {
+    let cols = region.left as usize..(region.left + region.width) as usize;
+    let rows = region.top as usize..(region.top + region.height) as usize;
+    for (y, row) in image.iter_rows_mut().enumerate() {
+        if rows.contains(&y) {
+            row[..cols.start].fill(pixel);
+            row[cols.end..].fill(pixel);
+        } else {
+            row.fill(pixel);
+        }
+    }
 }
 
 /// Create inner image container from part of given buffer.
 /// Buffer may be expanded if it size is less than required for image.
-fn get_temp_image_from_buffer<P: PixelExt>(
+pub(crate) fn get_temp_image_from_buffer<P: PixelExt>(
     buffer: &mut Vec<u8>,
     width: NonZeroU32,
     height: NonZeroU32,
@@ -205,26 +890,37 @@ fn get_temp_image_from_buffer<P: PixelExt>(
     InnerImage::new(width, height, &mut pixels[0..pixels_count])
 }
 
-fn resample_nearest<P>(src_image: &ImageView<P>, dst_image: &mut ImageViewMut<P>)
-where
+/// Resizes source image into the window of virtual destination image.
+pub(crate) fn resample_nearest<P>(
+    src_image: &ImageView<P>,
+    dst_image: &mut ImageViewMut<P>,
+    window: DstWindow,
+    alignment: PixelAlignment,
+) where
     P: PixelExt,
 {
     let crop_box = src_image.crop_box();
-    let dst_width = dst_image.width().get();
-    let x_scale = crop_box.width.get() as f64 / dst_width as f64;
-    let y_scale = crop_box.height.get() as f64 / dst_image.height().get() as f64;
+    let (x_in_start, x_step) = alignment.nearest_mapping(
+        crop_box.left,
+        crop_box.left + crop_box.width,
+        window.width.get(),
+    );
+    let (y_in_start, y_step) = alignment.nearest_mapping(
+        crop_box.top,
+        crop_box.top + crop_box.height,
+        window.height.get(),
+    );
 
     // Pretabulate horizontal pixel positions
-    let x_in_start = crop_box.left as f64 + x_scale * 0.5;
-    let max_src_x = src_image.width().get() as usize;
-    let x_in_tab: Vec<usize> = (0..dst_width)
-        .map(|x| ((x_in_start + x_scale * x as f64) as usize).min(max_src_x))
+    let max_src_x = src_image.width().get() as usize - 1;
+    let x_in_tab: Vec<usize> = window
+        .cols(dst_image.width())
+        .map(|x| ((x_in_start + x_step * x as f64) as usize).min(max_src_x))
         .collect();
 
-    let y_in_start = crop_box.top as f64 + y_scale * 0.5;
+    let dst_rows_range = window.rows(dst_image.height());
 
-    let src_rows =
-        src_image.iter_rows_with_step(y_in_start, y_scale, dst_image.height().get() as usize);
+    let src_rows = src_image.iter_rows_with_step(y_in_start, y_step, dst_rows_range);
     let dst_rows = dst_image.iter_rows_mut();
     for (out_row, in_row) in dst_rows.zip(src_rows) {
         for (&x_in, out_pixel) in x_in_tab.iter().zip(out_row.iter_mut()) {
@@ -234,143 +930,2160 @@ where
     }
 }
 
-fn resample_convolution<P>(
-    src_image: &ImageView<P>,
-    dst_image: &mut ImageViewMut<P>,
-    filter_type: FilterType,
-    cpu_extensions: CpuExtensions,
-    temp_buffer: &mut Vec<u8>,
-) where
-    P: Convolution,
-{
-    let crop_box = src_image.crop_box();
-    let dst_width = dst_image.width();
-    let dst_height = dst_image.height();
-    let (filter_fn, filter_support) = convolution::get_filter_func(filter_type);
-
-    let need_horizontal = dst_width != crop_box.width;
-    let horiz_coeffs = need_horizontal.then(|| {
-        convolution::precompute_coefficients(
-            src_image.width(),
-            crop_box.left as f64,
-            crop_box.left as f64 + crop_box.width.get() as f64,
-            dst_width,
-            filter_fn,
-            filter_support,
-        )
-    });
-
-    let need_vertical = dst_height != crop_box.height;
-    let vert_coeffs = need_vertical.then(|| {
-        convolution::precompute_coefficients(
-            src_image.height(),
-            crop_box.top as f64,
-            crop_box.top as f64 + crop_box.height.get() as f64,
-            dst_height,
-            filter_fn,
-            filter_support,
-        )
-    });
-
-    match (horiz_coeffs, vert_coeffs) {
-        (Some(horiz_coeffs), Some(mut vert_coeffs)) => {
-            let y_first = vert_coeffs.bounds[0].start;
-            // Last used row in the source image
-            let last_y_bound = vert_coeffs.bounds.last().unwrap();
-            let y_last = last_y_bound.start + last_y_bound.size;
-            let temp_height = NonZeroU32::new(y_last - y_first).unwrap();
-            let mut temp_image = get_temp_image_from_buffer(temp_buffer, dst_width, temp_height);
-            let mut tmp_dst_view = unsafe { temp_image.dst_view() };
-            P::horiz_convolution(
-                src_image,
-                &mut tmp_dst_view,
-                y_first,
-                horiz_coeffs,
-                cpu_extensions,
-            );
+/// Returns `true` if the source axis with given boundaries of crop box
+/// must be resampled to get the destination axis with given size.
+fn need_pass(in0: f64, in_size: f64, out_size: NonZeroU32) -> bool {
+    in_size != out_size.get() as f64 || in0.fract() != 0.
+}
 
-            // Shift bounds for vertical pass
-            vert_coeffs
-                .bounds
-                .iter_mut()
-                .for_each(|b| b.start -= y_first);
-            P::vert_convolution(
-                &tmp_dst_view.into(),
-                dst_image,
-                0,
-                vert_coeffs,
-                cpu_extensions,
-            );
-        }
-        (Some(horiz_coeffs), None) => {
-            P::horiz_convolution(
-                src_image,
-                dst_image,
-                crop_box.top,
-                horiz_coeffs,
-                cpu_extensions,
-            );
-        }
-        (None, Some(vert_coeffs)) => {
-            P::vert_convolution(
-                src_image,
-                dst_image,
-                crop_box.left,
-                vert_coeffs,
-                cpu_extensions,
-            );
-        }
-        _ => {}
-    }
+/// Returns `true` if the source image with given crop box must be
+/// resampled to get the destination image with given size.
+pub(crate) fn need_resize(
+    crop_box: CropBox,
+    dst_width: NonZeroU32,
+    dst_height: NonZeroU32,
+) -> bool {
+    need_pass(crop_box.left, crop_box.width, dst_width)
+        || need_pass(crop_box.top, crop_box.height, dst_height)
 }
 
-fn resample_super_sampling<P>(
-    src_image: &ImageView<P>,
-    dst_image: &mut ImageViewMut<P>,
-    filter_type: FilterType,
+/// Returns size of temporary image used by the first step of
+/// super-sampling, or `None` if there is no point in doing
+/// the resizing in two steps.
+pub(crate) fn super_sampling_size(
+    crop_box: CropBox,
+    dst_width: NonZeroU32,
+    dst_height: NonZeroU32,
     multiplicity: u8,
-    cpu_extensions: CpuExtensions,
-    temp_buffer: &mut Vec<u8>,
-    convolution_temp_buffer: &mut Vec<u8>,
-) where
-    P: Convolution,
-{
-    let crop_box = src_image.crop_box();
-    let dst_width = dst_image.width().get();
-    let dst_height = dst_image.height().get();
-    let width_scale = crop_box.width.get() as f32 / dst_width as f32;
-    let height_scale = crop_box.height.get() as f32 / dst_height as f32;
+) -> Option<(NonZeroU32, NonZeroU32)> {
+    let width_scale = crop_box.width as f32 / dst_width.get() as f32;
+    let height_scale = crop_box.height as f32 / dst_height.get() as f32;
     // It makes sense to resize the image in two steps only if the image
     // size is greater than the required size by multiplicity times.
     let factor = width_scale.min(height_scale) / multiplicity as f32;
     if factor > 1.2 {
-        // First step is resizing the source image by fastest algorithm.
-        // The temporary image will be about ``multiplicity`` times larger
-        // than required.
-        let tmp_width =
-            NonZeroU32::new((crop_box.width.get() as f32 / factor).round() as u32).unwrap();
-        let tmp_height =
-            NonZeroU32::new((crop_box.height.get() as f32 / factor).round() as u32).unwrap();
-
-        let mut tmp_img = get_temp_image_from_buffer(temp_buffer, tmp_width, tmp_height);
-        resample_nearest(src_image, unsafe { &mut tmp_img.dst_view() });
-        // Second step is resizing the temporary image with a convolution.
-        resample_convolution(
-            unsafe { &tmp_img.src_view() },
-            dst_image,
-            filter_type,
+        let tmp_width = NonZeroU32::new((crop_box.width as f32 / factor).round() as u32).unwrap();
+        let tmp_height = NonZeroU32::new((crop_box.height as f32 / factor).round() as u32).unwrap();
+        Some((tmp_width, tmp_height))
+    } else {
+        None
+    }
+}
+
+/// Parameters of convolution which don't depend on sizes of images.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ConvolutionParams {
+    pub horizontal: AxisAlg,
+    pub vertical: AxisAlg,
+    /// Factor of the filter support.
+    pub blur: f64,
+    pub alignment: PixelAlignment,
+    pub edge_mode: EdgeMode,
+}
+
+/// Parameters which uniquely define coefficients of one pass of convolution.
+#[derive(Debug, Clone, PartialEq)]
+struct CoefficientsKey {
+    in_size: NonZeroU32,
+    in0: f64,
+    in1: f64,
+    out_size: NonZeroU32,
+    alg: AxisAlg,
+    blur: f64,
+    alignment: PixelAlignment,
+    edge_mode: EdgeMode,
+}
+
+impl CoefficientsKey {
+    /// Returns keys of horizontal and vertical passes.
+    /// The pass is not required if its key is `None`.
+    fn for_passes(
+        src_width: NonZeroU32,
+        src_height: NonZeroU32,
+        crop_box: CropBox,
+        dst_width: NonZeroU32,
+        dst_height: NonZeroU32,
+        params: ConvolutionParams,
+    ) -> (Option<Self>, Option<Self>) {
+        let need_horizontal = need_pass(crop_box.left, crop_box.width, dst_width);
+        let horiz_key = need_horizontal.then(|| Self {
+            in_size: src_width,
+            in0: crop_box.left,
+            in1: crop_box.left + crop_box.width,
+            out_size: dst_width,
+            alg: params.horizontal,
+            blur: params.blur,
+            alignment: params.alignment,
+            edge_mode: params.edge_mode,
+        });
+
+        let need_vertical = need_pass(crop_box.top, crop_box.height, dst_height);
+        let vert_key = need_vertical.then(|| Self {
+            in_size: src_height,
+            in0: crop_box.top,
+            in1: crop_box.top + crop_box.height,
+            out_size: dst_height,
+            alg: params.vertical,
+            blur: params.blur,
+            alignment: params.alignment,
+            edge_mode: params.edge_mode,
+        });
+        (horiz_key, vert_key)
+    }
+
+    fn compute(&self) -> Coefficients {
+        let filter_type = match &self.alg {
+            AxisAlg::Nearest => {
+                return convolution::nearest_coefficients(
+                    self.in_size,
+                    self.in0,
+                    self.in1,
+                    self.out_size,
+                    self.alignment,
+                )
+            }
+            AxisAlg::Convolution(filter_type) => filter_type,
+        };
+        if !filter_type.is_valid() {
+            error!("Parameters of filter {:?} are not valid", filter_type);
+        }
+        let (filter_fn, filter_support) = convolution::get_filter_func(filter_type);
+        // Coefficients of wrapped axis are calculated for the padded axis
+        let padding = self.wrap_padding();
+        convolution::precompute_coefficients(
+            self.in_size.saturating_add(2 * padding),
+            self.in0 + padding as f64,
+            self.in1 + padding as f64,
+            self.out_size,
+            &filter_fn,
+            filter_support,
+            self.blur,
+            self.alignment,
+            self.edge_mode,
+        )
+    }
+
+    /// Returns the count of pixels added on each side of source axis
+    /// to convolve it with [`EdgeMode::Wrap`].
+    fn wrap_padding(&self) -> u32 {
+        match (&self.alg, self.edge_mode) {
+            (AxisAlg::Convolution(filter_type), EdgeMode::Wrap) => {
+                let (_, filter_support) = convolution::get_filter_func(filter_type);
+                let scale = (self.in1 - self.in0) / self.out_size.get() as f64;
+                (filter_support * scale.max(1.) * self.blur).ceil() as u32 + 1
+            }
+            _ => 0,
+        }
+    }
+}
+
+/// Coefficients of the last used geometry of each pass of convolution.
+#[derive(Debug, Clone, Default)]
+struct CoefficientsCache {
+    horiz: Option<(CoefficientsKey, Coefficients)>,
+    vert: Option<(CoefficientsKey, Coefficients)>,
+}
+
+impl CoefficientsCache {
+    fn passes(
+        &mut self,
+        src_width: NonZeroU32,
+        src_height: NonZeroU32,
+        crop_box: CropBox,
+        dst_width: NonZeroU32,
+        dst_height: NonZeroU32,
+        params: ConvolutionParams,
+    ) -> ConvolutionPasses {
+        let (horiz_key, vert_key) = CoefficientsKey::for_passes(
+            src_width, src_height, crop_box, dst_width, dst_height, params,
+        );
+        ConvolutionPasses {
+            wrap_padding: wrap_padding(&horiz_key, &vert_key),
+            horiz_coeffs: horiz_key.map(|key| Self::get_or_compute(&mut self.horiz, key)),
+            vert_coeffs: vert_key.map(|key| Self::get_or_compute(&mut self.vert, key)),
+        }
+    }
+
+    fn get_or_compute(
+        cached: &mut Option<(CoefficientsKey, Coefficients)>,
+        key: CoefficientsKey,
+    ) -> Coefficients {
+        match cached {
+            Some((cached_key, coeffs)) if *cached_key == key => coeffs.clone(),
+            _ => {
+                let coeffs = key.compute();
+                *cached = Some((key, coeffs.clone()));
+                coeffs
+            }
+        }
+    }
+}
+
+/// Returns the count of columns and rows added on each side of source
+/// image to convolve it with [`EdgeMode::Wrap`].
+fn wrap_padding(
+    horiz_key: &Option<CoefficientsKey>,
+    vert_key: &Option<CoefficientsKey>,
+) -> Option<(u32, u32)> {
+    let horiz_padding = horiz_key.as_ref().map_or(0, |key| key.wrap_padding());
+    let vert_padding = vert_key.as_ref().map_or(0, |key| key.wrap_padding());
+    (horiz_padding > 0 || vert_padding > 0).then_some((horiz_padding, vert_padding))
+}
+
+/// Coefficients of passes of convolution.
+/// The pass is not required if its coefficients are `None`.
+#[derive(Debug, Clone)]
+pub(crate) struct ConvolutionPasses {
+    pub horiz_coeffs: Option<Coefficients>,
+    pub vert_coeffs: Option<Coefficients>,
+    /// Count of columns and rows of wrapped pixels added on each side
+    /// of source image. Coefficients are calculated for the padded image.
+    pub wrap_padding: Option<(u32, u32)>,
+}
+
+impl ConvolutionPasses {
+    pub fn new(
+        src_width: NonZeroU32,
+        src_height: NonZeroU32,
+        crop_box: CropBox,
+        dst_width: NonZeroU32,
+        dst_height: NonZeroU32,
+        params: ConvolutionParams,
+    ) -> Self {
+        let (horiz_key, vert_key) = CoefficientsKey::for_passes(
+            src_width, src_height, crop_box, dst_width, dst_height, params,
+        );
+        Self {
+            wrap_padding: wrap_padding(&horiz_key, &vert_key),
+            horiz_coeffs: horiz_key.map(|key| key.compute()),
+            vert_coeffs: vert_key.map(|key| key.compute()),
+        }
+    }
+
+    /// Sets the type used to accumulate values of pixels
+    /// with floating-point components.
+    pub fn with_float_accumulation(mut self, float_accumulation: FloatAccumulation) -> Self {
+        let coeffs = self
+            .horiz_coeffs
+            .iter_mut()
+            .chain(self.vert_coeffs.iter_mut());
+        coeffs.for_each(|c| c.float_accumulation = float_accumulation);
+        self
+    }
+
+    /// Calculates in advance the coefficients normalized into integers
+    /// which are used by convolution of pixels of type `P`.
+    pub fn normalize_for<P: PixelExt>(&self) {
+        let coeffs = self.horiz_coeffs.iter().chain(self.vert_coeffs.iter());
+        coeffs.for_each(|c| c.normalize_for::<P>());
+    }
+
+    /// Returns passes required to calculate given rows of destination image.
+    pub fn slice(&self, dst_rows: Range<u32>) -> Self {
+        Self {
+            wrap_padding: self.wrap_padding,
+            horiz_coeffs: self.horiz_coeffs.clone(),
+            vert_coeffs: self
+                .vert_coeffs
+                .as_ref()
+                .map(|c| c.slice(dst_rows.start as usize..dst_rows.end as usize)),
+        }
+    }
+
+    /// Returns passes required to calculate given columns of destination image.
+    pub fn slice_columns(&self, dst_cols: Range<u32>) -> Self {
+        Self {
+            wrap_padding: self.wrap_padding,
+            horiz_coeffs: self
+                .horiz_coeffs
+                .as_ref()
+                .map(|c| c.slice(dst_cols.start as usize..dst_cols.end as usize)),
+            vert_coeffs: self.vert_coeffs.clone(),
+        }
+    }
+
+    /// Returns passes required to calculate destination image
+    /// with given size placed into the window of virtual image.
+    /// Passes are sliced only if destination image is smaller
+    /// than the virtual one.
+    pub fn window(self, window: DstWindow, dst_width: NonZeroU32, dst_height: NonZeroU32) -> Self {
+        let mut passes = self;
+        if dst_width != window.width {
+            passes = passes.slice_columns(window.cols(dst_width));
+        }
+        if dst_height != window.height {
+            passes = passes.slice(window.rows(dst_height));
+        }
+        passes
+    }
+
+    /// Returns rows of source image required to calculate the rows
+    /// of destination image. Coefficients of vertical pass must be
+    /// sliced for these rows.
+    pub fn src_rows(&self, crop_box: CropBox, dst_rows: Range<u32>) -> Range<u32> {
+        match &self.vert_coeffs {
+            Some(vert_coeffs) => {
+                let y_first = vert_coeffs.bounds[0].start;
+                // Last used row in the source image
+                let last_y_bound = vert_coeffs.bounds.last().unwrap();
+                y_first..last_y_bound.start + last_y_bound.size
+            }
+            None => {
+                // Without vertical pass the top of crop box is an integer
+                let top = crop_box.top as u32;
+                top + dst_rows.start..top + dst_rows.end
+            }
+        }
+    }
+
+    /// Shifts bounds of vertical pass to use source image
+    /// without the first `rows` rows.
+    pub fn skip_src_rows(&mut self, rows: u32) {
+        if let Some(vert_coeffs) = self.vert_coeffs.as_mut() {
+            vert_coeffs.shift_bounds(rows);
+        }
+    }
+}
+
+/// Functions used to multiply and divide pixels by alpha-channel.
+pub(crate) type MultiplyAlphaFn<P> = fn(&ImageView<P>, &mut ImageViewMut<P>, CpuExtensions);
+pub(crate) type DivideAlphaFn<P> = fn(&mut ImageViewMut<P>, CpuExtensions);
+
+/// Function used to convolve pixels excluding "no data" components.
+pub(crate) type NoDataConvolutionFn<P> =
+    fn(&ImageView<P>, &mut ImageViewMut<P>, u32, Coefficients, NoData);
+
+/// Options of execution of convolution.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ConvolutionOptions<P: PixelExt> {
+    pub cpu_extensions: CpuExtensions,
+    pub threads: NonZeroUsize,
+    /// Functions to multiply source image by alpha-channel
+    /// and divide destination image by alpha-channel.
+    pub alpha: Option<(MultiplyAlphaFn<P>, DivideAlphaFn<P>)>,
+    /// "No data" value and functions of horizontal and vertical passes
+    /// which exclude "no data" components.
+    pub nodata: Option<(NoData, NoDataConvolutionFn<P>, NoDataConvolutionFn<P>)>,
+}
+
+impl<P: PixelExt> ConvolutionOptions<P> {
+    pub fn new(cpu_extensions: CpuExtensions, threads: NonZeroUsize) -> Self {
+        Self {
+            cpu_extensions,
+            threads,
+            alpha: None,
+            nodata: None,
+        }
+    }
+
+    pub fn with_alpha(mut self) -> Self
+    where
+        P: AlphaMulDiv,
+    {
+        self.alpha = Some((P::multiply_alpha, P::divide_alpha_inplace));
+        self
+    }
+
+    pub fn with_nodata(mut self, nodata: NoData) -> Self
+    where
+        P: NoDataConvolution,
+    {
+        self.nodata = Some((
+            nodata,
+            P::horiz_convolution_nodata,
+            P::vert_convolution_nodata,
+        ));
+        self
+    }
+}
+
+/// Count of rows of source image which are multiplied by alpha-channel
+/// at once. SIMD-versions of convolution process rows by the same groups.
+const ALPHA_ROWS_GROUP: u32 = 4;
+
+/// Buffers reused by consequent calls of convolution.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ConvolutionBuffers {
+    /// Temporary image with the result of horizontal pass.
+    pub temp: Vec<u8>,
+    /// Rows of source image padded by wrapped pixels.
+    pub wrapped: Vec<u8>,
+    /// Buffers of threads for groups of source rows multiplied
+    /// by alpha-channel.
+    pub rows: Vec<Vec<u8>>,
+}
+
+impl ConvolutionBuffers {
+    fn capacity(&self) -> usize {
+        self.temp.capacity()
+            + self.wrapped.capacity()
+            + self.rows.iter().map(Vec::capacity).sum::<usize>()
+    }
+}
+
+/// Steps performed with each stripe of image by one pass of convolution.
+#[derive(Clone, Copy)]
+struct PassSteps<P: PixelExt> {
+    /// Multiplication of source rows by alpha-channel before the pass.
+    multiply: Option<MultiplyAlphaFn<P>>,
+    /// Division of destination rows by alpha-channel after the pass.
+    divide: Option<DivideAlphaFn<P>>,
+    /// Convolution which excludes "no data" components.
+    nodata: Option<(NoData, NoDataConvolutionFn<P>)>,
+}
+
+/// Resizes source image with a convolution into the window
+/// of virtual destination image.
+/// Coefficients of passes must be sliced for this window.
+pub(crate) fn convolve<P>(
+    src_image: &ImageView<P>,
+    dst_image: &mut ImageViewMut<P>,
+    window: DstWindow,
+    mut passes: ConvolutionPasses,
+    options: ConvolutionOptions<P>,
+    buffers: &mut ConvolutionBuffers,
+) where
+    P: Convolution,
+{
+    let ConvolutionOptions {
+        cpu_extensions,
+        threads,
+        alpha,
+        nodata,
+    } = options;
+    let crop_box = src_image.crop_box();
+    let mut src_rows = passes.src_rows(crop_box, window.rows(dst_image.height()));
+
+    let ConvolutionBuffers {
+        temp: temp_buffer,
+        wrapped: wrapped_buffer,
+        rows: rows_buffers,
+    } = buffers;
+
+    // Rows of source image padded by wrapped pixels
+    let wrapped_image;
+    let wrapped_view;
+    let src_image = match passes.wrap_padding {
+        Some(padding) => {
+            wrapped_image = wrap_src_rows(src_image, src_rows.clone(), padding, wrapped_buffer);
+            wrapped_view = unsafe { wrapped_image.src_view() };
+            passes.skip_src_rows(src_rows.start);
+            src_rows = 0..src_rows.end - src_rows.start;
+            &wrapped_view
+        }
+        None => src_image,
+    };
+
+    if passes.horiz_coeffs.is_some() {
+        // Vertical pass uses only part of the temporary image
+        passes.skip_src_rows(src_rows.start);
+    }
+    let temp_height = NonZeroU32::new(src_rows.end - src_rows.start).unwrap();
+    match (passes.horiz_coeffs, passes.vert_coeffs) {
+        (Some(horiz_coeffs), Some(vert_coeffs)) => {
+            let mut temp_image =
+                get_temp_image_from_buffer(temp_buffer, dst_image.width(), temp_height);
+            let mut tmp_dst_view = unsafe { temp_image.dst_view() };
+            let steps = PassSteps {
+                multiply: alpha.map(|(multiply, _)| multiply),
+                divide: None,
+                nodata: nodata.map(|(nodata, horiz, _)| (nodata, horiz)),
+            };
+            horiz_convolution_in_stripes(
+                src_image,
+                &mut tmp_dst_view,
+                src_rows.start,
+                horiz_coeffs,
+                cpu_extensions,
+                threads,
+                steps,
+                rows_buffers,
+            );
+            let steps = PassSteps {
+                multiply: None,
+                divide: alpha.map(|(_, divide)| divide),
+                nodata: nodata.map(|(nodata, _, vert)| (nodata, vert)),
+            };
+            vert_convolution_in_stripes(
+                &tmp_dst_view.into(),
+                dst_image,
+                0,
+                vert_coeffs,
+                cpu_extensions,
+                threads,
+                steps,
+                rows_buffers,
+            );
+        }
+        (Some(horiz_coeffs), None) => {
+            let steps = PassSteps {
+                multiply: alpha.map(|(multiply, _)| multiply),
+                divide: alpha.map(|(_, divide)| divide),
+                nodata: nodata.map(|(nodata, horiz, _)| (nodata, horiz)),
+            };
+            horiz_convolution_in_stripes(
+                src_image,
+                dst_image,
+                src_rows.start,
+                horiz_coeffs,
+                cpu_extensions,
+                threads,
+                steps,
+                rows_buffers,
+            );
+        }
+        (None, Some(vert_coeffs)) => {
+            let steps = PassSteps {
+                multiply: alpha.map(|(multiply, _)| multiply),
+                divide: alpha.map(|(_, divide)| divide),
+                nodata: nodata.map(|(nodata, _, vert)| (nodata, vert)),
+            };
+            vert_convolution_in_stripes(
+                src_image,
+                dst_image,
+                crop_box.left as u32 + window.left,
+                vert_coeffs,
+                cpu_extensions,
+                threads,
+                steps,
+                rows_buffers,
+            );
+        }
+        _ => {}
+    }
+}
+
+/// Returns copy of given rows of the source image padded by `padding`
+/// columns and rows with pixels wrapped from the opposite sides
+/// of the image. Indexes of rows are given for the padded image.
+fn wrap_src_rows<'a, P: PixelExt>(
+    src_image: &ImageView<P>,
+    rows: Range<u32>,
+    padding: (u32, u32),
+    buffer: &'a mut Vec<u8>,
+) -> InnerImage<'a, P> {
+    let src_width = src_image.width().get();
+    let src_height = src_image.height().get();
+    let width = NonZeroU32::new(src_width + 2 * padding.0).unwrap();
+    let height = NonZeroU32::new(rows.end - rows.start).unwrap();
+    let mut image = get_temp_image_from_buffer(buffer, width, height);
+    let mut dst_view = unsafe { image.dst_view() };
+    let first_src_x = EdgeMode::Wrap.map_index(-(padding.0 as i64), src_width) as usize;
+    for (y, mut dst_row) in rows.zip(dst_view.iter_rows_mut()) {
+        let src_y = EdgeMode::Wrap.map_index(y as i64 - padding.1 as i64, src_height);
+        let src_row = src_image.get_row(src_y).unwrap();
+        // Row is copied by parts: the right edge of source row,
+        // the whole row and the left edge of it. Padding may be
+        // wider than the image, so the row can be repeated many times.
+        let mut src_x = first_src_x;
+        while !dst_row.is_empty() {
+            let len = (src_row.len() - src_x).min(dst_row.len());
+            let (dst_part, rest) = dst_row.split_at_mut(len);
+            dst_part.copy_from_slice(&src_row[src_x..src_x + len]);
+            dst_row = rest;
+            src_x = 0;
+        }
+    }
+    image
+}
+
+/// Returns the height of stripes to split the image with given height
+/// between threads, or `None` if the image should be processed
+/// by the current thread only.
+pub(crate) fn get_stripe_height(height: NonZeroU32, threads: NonZeroUsize) -> Option<NonZeroU32> {
+    let threads = (threads.get() as u32).min(height.get());
+    if threads < 2 {
+        return None;
+    }
+    NonZeroU32::new(height.get().div_ceil(threads))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn horiz_convolution_in_stripes<P>(
+    src_image: &ImageView<P>,
+    dst_image: &mut ImageViewMut<P>,
+    offset: u32,
+    coeffs: Coefficients,
+    cpu_extensions: CpuExtensions,
+    threads: NonZeroUsize,
+    steps: PassSteps<P>,
+    rows_buffers: &mut Vec<Vec<u8>>,
+) where
+    P: Convolution,
+{
+    let Some(stripe_height) = get_stripe_height(dst_image.height(), threads) else {
+        let buffer = rows_buffer(rows_buffers, 1);
+        horiz_convolution(
+            src_image,
+            dst_image,
+            offset,
+            coeffs,
             cpu_extensions,
-            convolution_temp_buffer,
+            steps,
+            buffer,
         );
-    } else {
-        // There is no point in doing the resizing in two steps.
-        // We immediately resize the original image with a convolution.
-        resample_convolution(
+        return;
+    };
+    let stripes = dst_image.split_by_height(stripe_height);
+    rows_buffer(rows_buffers, stripes.len());
+    thread::scope(|scope| {
+        let stripes = stripes.into_iter().zip(rows_buffers.iter_mut());
+        for (i, (mut stripe, buffer)) in stripes.enumerate() {
+            // Each row of destination image depends only on
+            // the same row of source image.
+            let stripe_offset = offset + i as u32 * stripe_height.get();
+            let coeffs = coeffs.clone();
+            scope.spawn(move || {
+                horiz_convolution(
+                    src_image,
+                    &mut stripe,
+                    stripe_offset,
+                    coeffs,
+                    cpu_extensions,
+                    steps,
+                    buffer,
+                )
+            });
+        }
+    });
+}
+
+/// Returns the first of buffers of rows which count is increased
+/// to `count` if required.
+fn rows_buffer(rows_buffers: &mut Vec<Vec<u8>>, count: usize) -> &mut Vec<u8> {
+    if rows_buffers.len() < count {
+        rows_buffers.resize_with(count, Vec::new);
+    }
+    &mut rows_buffers[0]
+}
+
+fn horiz_convolution<P>(
+    src_image: &ImageView<P>,
+    dst_image: &mut ImageViewMut<P>,
+    offset: u32,
+    coeffs: Coefficients,
+    cpu_extensions: CpuExtensions,
+    steps: PassSteps<P>,
+    buffer: &mut Vec<u8>,
+) where
+    P: Convolution,
+{
+    match steps.multiply {
+        Some(multiply) => {
+            // Small groups of rows of source image are multiplied
+            // by alpha into the buffer just before the pass.
+            let mut src_y = offset;
+            let group_height = NonZeroU32::new(ALPHA_ROWS_GROUP).unwrap();
+            for mut dst_rows in dst_image.split_by_height(group_height) {
+                let height = dst_rows.height();
+                let src_rows = src_image.rows_view(src_y..src_y + height.get());
+                src_y += height.get();
+                let mut rows_image = get_temp_image_from_buffer(buffer, src_rows.width(), height);
+                multiply(
+                    &src_rows,
+                    unsafe { &mut rows_image.dst_view() },
+                    cpu_extensions,
+                );
+                let rows_view = unsafe { rows_image.src_view() };
+                let coeffs = coeffs.clone();
+                horiz_pass(&rows_view, &mut dst_rows, 0, coeffs, cpu_extensions, steps);
+            }
+        }
+        None => horiz_pass(src_image, dst_image, offset, coeffs, cpu_extensions, steps),
+    }
+    if let Some(divide) = steps.divide {
+        divide(dst_image, cpu_extensions);
+    }
+}
+
+#[inline(always)]
+fn horiz_pass<P>(
+    src_image: &ImageView<P>,
+    dst_image: &mut ImageViewMut<P>,
+    offset: u32,
+    coeffs: Coefficients,
+    cpu_extensions: CpuExtensions,
+    steps: PassSteps<P>,
+) where
+    P: Convolution,
+{
+    match steps.nodata {
+        Some((nodata, convolution)) => convolution(src_image, dst_image, offset, coeffs, nodata),
+        None => P::horiz_convolution(src_image, dst_image, offset, coeffs, cpu_extensions),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn vert_convolution_in_stripes<P>(
+    src_image: &ImageView<P>,
+    dst_image: &mut ImageViewMut<P>,
+    offset: u32,
+    coeffs: Coefficients,
+    cpu_extensions: CpuExtensions,
+    threads: NonZeroUsize,
+    steps: PassSteps<P>,
+    rows_buffers: &mut Vec<Vec<u8>>,
+) where
+    P: Convolution,
+{
+    let Some(stripe_height) = get_stripe_height(dst_image.height(), threads) else {
+        let buffer = rows_buffer(rows_buffers, 1);
+        vert_convolution(
             src_image,
             dst_image,
-            filter_type,
+            offset,
+            coeffs,
             cpu_extensions,
-            convolution_temp_buffer,
+            steps,
+            buffer,
         );
+        return;
+    };
+    let stripes = dst_image.split_by_height(stripe_height);
+    rows_buffer(rows_buffers, stripes.len());
+    thread::scope(|scope| {
+        let mut first_row = 0;
+        for (mut stripe, buffer) in stripes.into_iter().zip(rows_buffers.iter_mut()) {
+            // Bounds of coefficients define which rows of source image
+            // are required to calculate rows of the stripe.
+            let last_row = first_row + stripe.height().get() as usize;
+            let coeffs = coeffs.slice(first_row..last_row);
+            first_row = last_row;
+            scope.spawn(move || {
+                vert_convolution(
+                    src_image,
+                    &mut stripe,
+                    offset,
+                    coeffs,
+                    cpu_extensions,
+                    steps,
+                    buffer,
+                )
+            });
+        }
+    });
+}
+
+fn vert_convolution<P>(
+    src_image: &ImageView<P>,
+    dst_image: &mut ImageViewMut<P>,
+    offset: u32,
+    coeffs: Coefficients,
+    cpu_extensions: CpuExtensions,
+    steps: PassSteps<P>,
+    buffer: &mut Vec<u8>,
+) where
+    P: Convolution,
+{
+    match steps.multiply {
+        Some(multiply) => {
+            // Rows of source image required by small groups of rows
+            // of destination image are multiplied by alpha into
+            // the buffer just before the pass.
+            let mut first_row = 0;
+            let group_height = NonZeroU32::new(ALPHA_ROWS_GROUP).unwrap();
+            for mut dst_rows in dst_image.split_by_height(group_height) {
+                let last_row = first_row + dst_rows.height().get() as usize;
+                let mut coeffs = coeffs.slice(first_row..last_row);
+                first_row = last_row;
+                let src_y = coeffs.bounds.iter().map(|b| b.start).min().unwrap();
+                let src_end = coeffs
+                    .bounds
+                    .iter()
+                    .map(|b| b.start + b.size)
+                    .max()
+                    .unwrap();
+                coeffs.shift_bounds(src_y);
+                // Only columns used by the pass are multiplied
+                let src_rows = src_image.rows_view(src_y..src_end);
+                let columns = CropBox {
+                    left: offset as f64,
+                    top: 0.,
+                    width: dst_rows.width().get() as f64,
+                    height: src_rows.height().get() as f64,
+                };
+                let src_rows = unsafe { src_rows.sub_view(columns) };
+                let mut rows_image =
+                    get_temp_image_from_buffer(buffer, src_rows.width(), src_rows.height());
+                multiply(
+                    &src_rows,
+                    unsafe { &mut rows_image.dst_view() },
+                    cpu_extensions,
+                );
+                let rows_view = unsafe { rows_image.src_view() };
+                vert_pass(&rows_view, &mut dst_rows, 0, coeffs, cpu_extensions, steps);
+            }
+        }
+        None => vert_pass(src_image, dst_image, offset, coeffs, cpu_extensions, steps),
+    }
+    if let Some(divide) = steps.divide {
+        divide(dst_image, cpu_extensions);
+    }
+}
+
+#[inline(always)]
+fn vert_pass<P>(
+    src_image: &ImageView<P>,
+    dst_image: &mut ImageViewMut<P>,
+    offset: u32,
+    coeffs: Coefficients,
+    cpu_extensions: CpuExtensions,
+    steps: PassSteps<P>,
+) where
+    P: Convolution,
+{
+    match steps.nodata {
+        Some((nodata, convolution)) => convolution(src_image, dst_image, offset, coeffs, nodata),
+        None => P::vert_convolution(src_image, dst_image, offset, coeffs, cpu_extensions),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::pixels::{F32x2, F32x3, F32x4, PixelType, U8x3, U8x4, F32, I32, U16};
+    use crate::{CropBox, DynamicImage, Filter, Image, MulDiv};
+
+    #[test]
+    fn test_resize_with_alpha() {
+        let src_width = NonZeroU32::new(40).unwrap();
+        let src_height = NonZeroU32::new(30).unwrap();
+        let buffer: Vec<u8> = (0..src_width.get() * src_height.get() * 4)
+            .map(|i| (i * 37 % 251) as u8)
+            .collect();
+        let src_image = Image::<U8x4>::try_from_vec_u8(src_width, src_height, buffer).unwrap();
+        let mut premultiplied = Image::<U8x4>::new(src_width, src_height);
+        let mul_div = MulDiv::default();
+        mul_div
+            .try_multiply_alpha(
+                &src_image.try_view().unwrap(),
+                &mut premultiplied.try_view_mut().unwrap(),
+            )
+            .unwrap();
+
+        for (dst_width, dst_height) in [(13, 11), (13, 30), (40, 11), (70, 50)] {
+            let dst_width = NonZeroU32::new(dst_width).unwrap();
+            let dst_height = NonZeroU32::new(dst_height).unwrap();
+            for threads in [1, 3] {
+                let mut resizer = Resizer::default();
+                resizer.set_threads(NonZeroUsize::new(threads).unwrap());
+
+                let mut expected = Image::<U8x4>::new(dst_width, dst_height);
+                let mut dst_view = expected.try_view_mut().unwrap();
+                unsafe { resizer.resize(&premultiplied.try_view().unwrap(), &mut dst_view) };
+                mul_div.divide_alpha_inplace(&mut dst_view);
+
+                let mut result = Image::<U8x4>::new(dst_width, dst_height);
+                unsafe {
+                    resizer.resize_with_alpha(
+                        &src_image.try_view().unwrap(),
+                        &mut result.try_view_mut().unwrap(),
+                    )
+                };
+                assert_eq!(result.buffer(), expected.buffer());
+            }
+        }
+    }
+
+    fn resize_constant_f32<P: Convolution>(
+        resizer: &mut Resizer,
+        src_size: (u32, u32),
+        dst_size: (u32, u32),
+    ) -> Vec<f32> {
+        let src_width = NonZeroU32::new(src_size.0).unwrap();
+        let src_height = NonZeroU32::new(src_size.1).unwrap();
+        let components = src_width.get() * src_height.get() * P::size() as u32 / 4;
+        let buffer: Vec<u8> = (0..components)
+            .flat_map(|_| 0.25f32.to_ne_bytes())
+            .collect();
+        let src_image = Image::<P>::try_from_vec_u8(src_width, src_height, buffer).unwrap();
+        let dst_width = NonZeroU32::new(dst_size.0).unwrap();
+        let dst_height = NonZeroU32::new(dst_size.1).unwrap();
+        let mut dst_image = Image::<P>::new(dst_width, dst_height);
+        unsafe {
+            resizer.resize(
+                &src_image.try_view().unwrap(),
+                &mut dst_image.try_view_mut().unwrap(),
+            )
+        };
+        dst_image
+            .buffer()
+            .chunks_exact(4)
+            .map(|c| f32::from_ne_bytes(c.try_into().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_fractional_float_results() {
+        let mut cpu_extensions_list = vec![CpuExtensions::None];
+        #[cfg(target_arch = "x86_64")]
+        cpu_extensions_list.extend([CpuExtensions::Sse4_1, CpuExtensions::Avx2]);
+        #[cfg(target_arch = "aarch64")]
+        cpu_extensions_list.push(CpuExtensions::Neon);
+        #[cfg(target_arch = "wasm32")]
+        cpu_extensions_list.push(CpuExtensions::Simd128);
+
+        let algorithms = [
+            ResizeAlg::Convolution(FilterType::Bilinear),
+            ResizeAlg::Convolution(FilterType::Lanczos3),
+            ResizeAlg::SuperSampling(FilterType::CatmullRom, 2),
+        ];
+        let sizes = [
+            ((64, 48), (17, 13)),
+            ((17, 13), (64, 48)),
+            ((40, 30), (40, 7)),
+        ];
+        for cpu_extensions in cpu_extensions_list {
+            if !cpu_extensions.is_supported() {
+                continue;
+            }
+            for float_accumulation in [FloatAccumulation::F64, FloatAccumulation::F32] {
+                for algorithm in algorithms.iter().cloned() {
+                    let mut resizer = Resizer::new(algorithm);
+                    unsafe { resizer.set_cpu_extensions(cpu_extensions) };
+                    resizer.set_float_accumulation(float_accumulation);
+                    for (src_size, dst_size) in sizes {
+                        let results = [
+                            resize_constant_f32::<F32>(&mut resizer, src_size, dst_size),
+                            resize_constant_f32::<F32x2>(&mut resizer, src_size, dst_size),
+                            resize_constant_f32::<F32x3>(&mut resizer, src_size, dst_size),
+                            resize_constant_f32::<F32x4>(&mut resizer, src_size, dst_size),
+                        ];
+                        for value in results.iter().flatten() {
+                            match float_accumulation {
+                                FloatAccumulation::F64 => assert_eq!(*value, 0.25),
+                                FloatAccumulation::F32 => assert!((value - 0.25).abs() < 1e-6),
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_resize_with_nodata() {
+        let size = NonZeroU32::new(40).unwrap();
+        let dst_size = NonZeroU32::new(10).unwrap();
+        // Left half of image is "no data", right half has a few
+        // "no data" pixels scattered over it.
+        let is_nodata = |i: u32| i % 40 < 20 || i % 7 == 0;
+
+        let buffer: Vec<u8> = (0..size.get() * size.get())
+            .flat_map(|i| if is_nodata(i) { f32::NAN } else { 2. }.to_ne_bytes())
+            .collect();
+        let src_f32 = Image::<F32>::try_from_vec_u8(size, size, buffer).unwrap();
+        let buffer: Vec<u8> = (0..size.get() * size.get())
+            .flat_map(|i| if is_nodata(i) { 0u16 } else { 1000 }.to_ne_bytes())
+            .collect();
+        let src_u16 = Image::<U16>::try_from_vec_u8(size, size, buffer).unwrap();
+
+        for threads in [1, 3] {
+            let mut resizer = Resizer::new(ResizeAlg::Convolution(FilterType::Bilinear));
+            resizer.set_threads(NonZeroUsize::new(threads).unwrap());
+
+            let mut dst_f32 = Image::<F32>::new(dst_size, dst_size);
+            let mut dst_u16 = Image::<U16>::new(dst_size, dst_size);
+            unsafe {
+                resizer.resize_with_nodata(
+                    &src_f32.try_view().unwrap(),
+                    &mut dst_f32.try_view_mut().unwrap(),
+                    NoData::Nan,
+                );
+                resizer.resize_with_nodata(
+                    &src_u16.try_view().unwrap(),
+                    &mut dst_u16.try_view_mut().unwrap(),
+                    NoData::Value(0.),
+                );
+            }
+            let values_f32 = dst_f32
+                .buffer()
+                .chunks_exact(4)
+                .map(|c| f32::from_ne_bytes(c.try_into().unwrap()));
+            let values_u16 = dst_u16
+                .buffer()
+                .chunks_exact(2)
+                .map(|c| u16::from_ne_bytes(c.try_into().unwrap()));
+            for (i, (value_f32, value_u16)) in values_f32.zip(values_u16).enumerate() {
+                // Windows of filter of the first 4 columns contain
+                // only "no data" pixels.
+                if i % 10 < 4 {
+                    assert!(value_f32.is_nan());
+                    assert_eq!(value_u16, 0);
+                } else {
+                    assert!((value_f32 - 2.).abs() < 1e-6);
+                    assert_eq!(value_u16, 1000);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_resize_with_nodata_negative_lobes() {
+        let size = NonZeroU32::new(40).unwrap();
+        let dst_size = NonZeroU32::new(17).unwrap();
+        // Every 4th column is "no data", columns between them are
+        // filled by 0 and 255 alternately. So every window of filter
+        // contains "no data" pixels and sharp edges between valid ones.
+        let value = |i: u32| match i % 40 % 8 {
+            0 | 4 => f32::NAN,
+            1..=3 => 0.,
+            _ => 255.,
+        };
+        let buffer: Vec<u8> = (0..size.get() * size.get())
+            .flat_map(|i| value(i).to_ne_bytes())
+            .collect();
+        let src_image = Image::<F32>::try_from_vec_u8(size, size, buffer).unwrap();
+
+        for threads in [1, 3] {
+            let mut resizer = Resizer::new(ResizeAlg::Convolution(FilterType::Lanczos3));
+            resizer.set_threads(NonZeroUsize::new(threads).unwrap());
+            let mut dst_image = Image::<F32>::new(dst_size, dst_size);
+            unsafe {
+                resizer.resize_with_nodata(
+                    &src_image.try_view().unwrap(),
+                    &mut dst_image.try_view_mut().unwrap(),
+                    NoData::Nan,
+                );
+            }
+            let values = dst_image
+                .buffer()
+                .chunks_exact(4)
+                .map(|c| f32::from_ne_bytes(c.try_into().unwrap()));
+            for (i, value) in values.enumerate() {
+                // Negative lobes of filter are excluded,
+                // so the result stays within the range of valid pixels.
+                assert!((-1e-3..=255.001).contains(&value), "{value} at {i}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_resize_with_nodata_value_f32() {
+        let width = NonZeroU32::new(8).unwrap();
+        let height = NonZeroU32::new(1).unwrap();
+        let buffer: Vec<u8> = [0f32, 2.]
+            .repeat(4)
+            .into_iter()
+            .flat_map(f32::to_ne_bytes)
+            .collect();
+        let src_image = Image::<F32>::try_from_vec_u8(width, height, buffer).unwrap();
+        let mut dst_image = Image::<F32>::new(NonZeroU32::new(4).unwrap(), height);
+        let mut resizer = Resizer::new(ResizeAlg::Convolution(FilterType::Box));
+        unsafe {
+            resizer.resize_with_nodata(
+                &src_image.try_view().unwrap(),
+                &mut dst_image.try_view_mut().unwrap(),
+                NoData::Value(1.),
+            );
+        }
+        // Average of valid pixels is equal to "no data" value,
+        // so it is moved to the nearest valid one.
+        for c in dst_image.buffer().chunks_exact(4) {
+            let value = f32::from_ne_bytes(c.try_into().unwrap());
+            assert_ne!(value, 1.);
+            assert!((value - 1.).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_resize_with_nodata_value_not_representable_in_f32() {
+        let width = NonZeroU32::new(6).unwrap();
+        let height = NonZeroU32::new(1).unwrap();
+        let nodata = -9999.9f32;
+        let buffer: Vec<u8> = [nodata, 3., 5., nodata, nodata, nodata]
+            .into_iter()
+            .flat_map(f32::to_ne_bytes)
+            .collect();
+        let src_image = Image::<F32>::try_from_vec_u8(width, height, buffer).unwrap();
+        let mut dst_image = Image::<F32>::new(NonZeroU32::new(3).unwrap(), height);
+        let mut resizer = Resizer::new(ResizeAlg::Convolution(FilterType::Box));
+        unsafe {
+            resizer.resize_with_nodata(
+                &src_image.try_view().unwrap(),
+                &mut dst_image.try_view_mut().unwrap(),
+                NoData::Value(-9999.9),
+            );
+        }
+        let result: Vec<f32> = dst_image
+            .buffer()
+            .chunks_exact(4)
+            .map(|c| f32::from_ne_bytes(c.try_into().unwrap()))
+            .collect();
+        assert_eq!(result, [3., 5., nodata]);
+    }
+
+    #[test]
+    fn test_custom_filter() {
+        let src_width = NonZeroU32::new(40).unwrap();
+        let src_height = NonZeroU32::new(30).unwrap();
+        let buffer: Vec<u8> = (0..src_width.get() * src_height.get() * 4)
+            .map(|i| (i * 37 % 251) as u8)
+            .collect();
+        let src_image = Image::<U8x4>::try_from_vec_u8(src_width, src_height, buffer).unwrap();
+        let dst_width = NonZeroU32::new(13).unwrap();
+        let dst_height = NonZeroU32::new(17).unwrap();
+        let resize = |resizer: &mut Resizer| {
+            let mut dst_image = Image::<U8x4>::new(dst_width, dst_height);
+            unsafe {
+                resizer.resize(
+                    &src_image.try_view().unwrap(),
+                    &mut dst_image.try_view_mut().unwrap(),
+                )
+            };
+            dst_image.into_vec()
+        };
+
+        let triangle = Filter::try_new(|x: f64| (1. - x.abs()).max(0.), 1.).unwrap();
+        let rectangle =
+            Filter::try_new(|x: f64| if x > -0.5 && x <= 0.5 { 1. } else { 0. }, 0.5).unwrap();
+        assert_eq!(triangle, triangle.clone());
+        assert_ne!(
+            triangle,
+            Filter::try_new(|x: f64| (1. - x.abs()).max(0.), 1.).unwrap()
+        );
+        assert_eq!(
+            Filter::try_new(|x| x, 0.).unwrap_err(),
+            Error::InvalidFilterSupport
+        );
+
+        let mut resizer = Resizer::default();
+        // The same instance of resizer is used to check that
+        // cached coefficients are not reused for another filter.
+        for (custom_filter, filter_type) in [
+            (triangle, FilterType::Bilinear),
+            (rectangle, FilterType::Box),
+        ] {
+            resizer.algorithm = ResizeAlg::Convolution(filter_type);
+            let expected = resize(&mut resizer);
+            resizer.algorithm = ResizeAlg::Convolution(FilterType::Custom(custom_filter));
+            assert_eq!(resize(&mut resizer), expected);
+        }
+    }
+
+    #[test]
+    fn test_custom_filter_with_nan() {
+        let triangle = |x: f64| (1. - x.abs()).max(0.);
+        let mut resizer = Resizer::default();
+        for func in [
+            Filter::new(move |x| if x == 0. { f64::NAN } else { triangle(x) }, 1.),
+            Filter::new(|_| f64::NAN, 1.),
+        ] {
+            resizer.algorithm = ResizeAlg::Convolution(FilterType::Custom(func));
+            for dst_size in [(13, 17), (1, 1), (50, 40)] {
+                resize_constant(&mut resizer, F32::new(0.5), dst_size);
+            }
+        }
+    }
+
+    fn resize_constant<P: Convolution>(resizer: &mut Resizer, pixel: P, dst_size: (u32, u32)) {
+        let src_width = NonZeroU32::new(37).unwrap();
+        let src_height = NonZeroU32::new(23).unwrap();
+        let src_pixels = vec![pixel; (src_width.get() * src_height.get()) as usize];
+        let src_view = ImageView::try_from_pixels(src_width, src_height, &src_pixels).unwrap();
+        let dst_width = NonZeroU32::new(dst_size.0).unwrap();
+        let dst_height = NonZeroU32::new(dst_size.1).unwrap();
+        // Destination image is filled with zeros
+        let mut dst_image = Image::<P>::new(dst_width, dst_height);
+        unsafe { resizer.resize(&src_view, &mut dst_image.try_view_mut().unwrap()) };
+        let dst_view = dst_image.try_view().unwrap();
+        let mut dst_pixels = dst_view.iter_rows(0).flatten();
+        assert!(
+            dst_pixels.all(|&p| p == pixel),
+            "{:?} {:?}",
+            resizer.algorithm,
+            resizer.cpu_extensions()
+        );
+    }
+
+    #[test]
+    fn test_additional_filters() {
+        let mut cpu_extensions_list = vec![CpuExtensions::None];
+        #[cfg(target_arch = "x86_64")]
+        cpu_extensions_list.extend([CpuExtensions::Sse4_1, CpuExtensions::Avx2]);
+        #[cfg(target_arch = "aarch64")]
+        cpu_extensions_list.push(CpuExtensions::Neon);
+        #[cfg(target_arch = "wasm32")]
+        cpu_extensions_list.push(CpuExtensions::Simd128);
+
+        let filters = [
+            FilterType::Lanczos2,
+            FilterType::Lanczos4,
+            FilterType::Gaussian { sigma: 0.5 },
+            FilterType::Gaussian { sigma: 1.5 },
+            FilterType::Spline16,
+            FilterType::Spline36,
+            FilterType::Spline64,
+            FilterType::BSpline,
+            FilterType::Blackman,
+            FilterType::Kaiser { beta: 4. },
+            FilterType::Cubic { b: 0., c: 0.75 },
+        ];
+        for cpu_extensions in cpu_extensions_list {
+            if !cpu_extensions.is_supported() {
+                continue;
+            }
+            for filter_type in filters.iter().cloned() {
+                let mut resizer = Resizer::new(ResizeAlg::Convolution(filter_type));
+                unsafe { resizer.set_cpu_extensions(cpu_extensions) };
+                for dst_size in [(11, 7), (80, 50)] {
+                    let u8x4 = U8x4::new(u32::from_le_bytes([10, 100, 200, 255]));
+                    resize_constant(&mut resizer, u8x4, dst_size);
+                    resize_constant(&mut resizer, U16::new(1000), dst_size);
+                    resize_constant(&mut resizer, I32::new(-12345), dst_size);
+                    let f32x4 = F32x4::new([0.25, 1.5, -3., 100.]);
+                    resize_constant(&mut resizer, f32x4, dst_size);
+                }
+            }
+        }
+    }
+
+    /// Resizes image with pseudo-random components and returns
+    /// components of the result.
+    fn resize_random<P: Convolution>(
+        resizer: &mut Resizer,
+        src_size: (u32, u32),
+        dst_size: (u32, u32),
+        component: impl Fn(u32) -> [u8; 4],
+    ) -> Vec<[u8; 4]> {
+        let src_width = NonZeroU32::new(src_size.0).unwrap();
+        let src_height = NonZeroU32::new(src_size.1).unwrap();
+        let mut seed = 12345u32;
+        let buffer: Vec<u8> = (0..src_width.get() * src_height.get())
+            .flat_map(|_| {
+                seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+                component(seed >> 8)
+            })
+            .collect();
+        let src_image = Image::<P>::try_from_vec_u8(src_width, src_height, buffer).unwrap();
+        let dst_width = NonZeroU32::new(dst_size.0).unwrap();
+        let dst_height = NonZeroU32::new(dst_size.1).unwrap();
+        let mut dst_image = Image::<P>::new(dst_width, dst_height);
+        unsafe {
+            resizer.resize(
+                &src_image.try_view().unwrap(),
+                &mut dst_image.try_view_mut().unwrap(),
+            )
+        };
+        dst_image
+            .buffer()
+            .chunks_exact(4)
+            .map(|c| c.try_into().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_simd_f32_and_i32() {
+        let mut cpu_extensions_list = vec![];
+        #[cfg(target_arch = "x86_64")]
+        cpu_extensions_list.extend([CpuExtensions::Sse4_1, CpuExtensions::Avx2]);
+        #[cfg(target_arch = "aarch64")]
+        cpu_extensions_list.push(CpuExtensions::Neon);
+        #[cfg(target_arch = "wasm32")]
+        cpu_extensions_list.push(CpuExtensions::Simd128);
+
+        let algorithms = [
+            ResizeAlg::Convolution(FilterType::Bilinear),
+            ResizeAlg::Convolution(FilterType::Lanczos3),
+        ];
+        let sizes = [
+            ((67, 45), (17, 13)),
+            ((17, 13), (67, 45)),
+            ((40, 31), (40, 7)),
+            ((40, 31), (9, 31)),
+        ];
+        let f32_component = |v: u32| (v as f32 / 65536. - 128.).to_ne_bytes();
+        let i32_component = |v: u32| ((v as i32) - (1 << 23)).wrapping_mul(251).to_ne_bytes();
+        for cpu_extensions in cpu_extensions_list {
+            if !cpu_extensions.is_supported() {
+                continue;
+            }
+            for float_accumulation in [FloatAccumulation::F64, FloatAccumulation::F32] {
+                for algorithm in algorithms.iter().cloned() {
+                    let mut native_resizer = Resizer::new(algorithm.clone());
+                    unsafe { native_resizer.set_cpu_extensions(CpuExtensions::None) };
+                    native_resizer.set_float_accumulation(float_accumulation);
+                    let mut simd_resizer = Resizer::new(algorithm);
+                    unsafe { simd_resizer.set_cpu_extensions(cpu_extensions) };
+                    simd_resizer.set_float_accumulation(float_accumulation);
+                    for (src_size, dst_size) in sizes {
+                        let expected = resize_random::<F32>(
+                            &mut native_resizer,
+                            src_size,
+                            dst_size,
+                            f32_component,
+                        );
+                        let result = resize_random::<F32>(
+                            &mut simd_resizer,
+                            src_size,
+                            dst_size,
+                            f32_component,
+                        );
+                        for (&expected, &value) in expected.iter().zip(&result) {
+                            let expected = f32::from_ne_bytes(expected);
+                            let value = f32::from_ne_bytes(value);
+                            assert!(
+                                (value - expected).abs() <= 1e-4,
+                                "{cpu_extensions:?} {float_accumulation:?}: {value} != {expected}"
+                            );
+                        }
+                    }
+                }
+            }
+            for algorithm in algorithms.iter().cloned() {
+                let mut native_resizer = Resizer::new(algorithm.clone());
+                unsafe { native_resizer.set_cpu_extensions(CpuExtensions::None) };
+                let mut simd_resizer = Resizer::new(algorithm);
+                unsafe { simd_resizer.set_cpu_extensions(cpu_extensions) };
+                for (src_size, dst_size) in sizes {
+                    let expected = resize_random::<I32>(
+                        &mut native_resizer,
+                        src_size,
+                        dst_size,
+                        i32_component,
+                    );
+                    let result =
+                        resize_random::<I32>(&mut simd_resizer, src_size, dst_size, i32_component);
+                    assert_eq!(result, expected, "{cpu_extensions:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_threads() {
+        let algorithms = [
+            ResizeAlg::Convolution(FilterType::Lanczos3),
+            ResizeAlg::SuperSampling(FilterType::Bilinear, 2),
+            ResizeAlg::Nearest,
+        ];
+        let sizes = [
+            ((67, 45), (17, 13)),
+            ((17, 13), (67, 45)),
+            ((40, 31), (40, 7)),
+            ((40, 31), (9, 31)),
+        ];
+        let u8_component = |v: u32| v.to_ne_bytes();
+        let f32_component = |v: u32| (v as f32 / 65536.).to_ne_bytes();
+        for algorithm in algorithms {
+            for (src_size, dst_size) in sizes {
+                let mut resizer = Resizer::new(algorithm.clone());
+                let expected_u8x4 =
+                    resize_random::<U8x4>(&mut resizer, src_size, dst_size, u8_component);
+                let expected_f32 =
+                    resize_random::<F32>(&mut resizer, src_size, dst_size, f32_component);
+                for threads in [2, 3, 8] {
+                    resizer.set_threads(NonZeroUsize::new(threads).unwrap());
+                    let result =
+                        resize_random::<U8x4>(&mut resizer, src_size, dst_size, u8_component);
+                    assert_eq!(result, expected_u8x4, "{algorithm:?} {threads}");
+                    let result =
+                        resize_random::<F32>(&mut resizer, src_size, dst_size, f32_component);
+                    assert_eq!(result, expected_f32, "{algorithm:?} {threads}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_resize_dynamic() {
+        let src_width = NonZeroU32::new(40).unwrap();
+        let src_height = NonZeroU32::new(30).unwrap();
+        let dst_width = NonZeroU32::new(13).unwrap();
+        let dst_height = NonZeroU32::new(17).unwrap();
+        let crop_box = CropBox {
+            left: 3.,
+            top: 2.,
+            width: 30.,
+            height: 20.,
+        };
+        let u8x3_buffer: Vec<u8> = (0..src_width.get() * src_height.get() * 3)
+            .map(|i| (i * 37 % 251) as u8)
+            .collect();
+        let f32_buffer: Vec<u8> = (0..src_width.get() * src_height.get())
+            .flat_map(|i| (i as f32 * 0.37 % 25.1).to_ne_bytes())
+            .collect();
+        let mut resizer = Resizer::new(ResizeAlg::Convolution(FilterType::Lanczos3));
+
+        for (pixel_type, buffer) in [
+            (PixelType::U8x3, &u8x3_buffer),
+            (PixelType::F32, &f32_buffer),
+        ] {
+            // Typed resizing of the same image
+            let mut expected = DynamicImage::new(dst_width, dst_height, pixel_type);
+            let mut expected_cropped = DynamicImage::new(dst_width, dst_height, pixel_type);
+            match (&mut expected, &mut expected_cropped) {
+                (DynamicImage::U8x3(dst), DynamicImage::U8x3(dst_cropped)) => {
+                    let src = ImageView::<U8x3>::try_new(src_width, src_height, buffer).unwrap();
+                    unsafe { resizer.resize(&src, &mut dst.try_view_mut().unwrap()) };
+                    let src = src.try_crop(crop_box).unwrap();
+                    unsafe { resizer.resize(&src, &mut dst_cropped.try_view_mut().unwrap()) };
+                }
+                (DynamicImage::F32(dst), DynamicImage::F32(dst_cropped)) => {
+                    let src = ImageView::<F32>::try_new(src_width, src_height, buffer).unwrap();
+                    unsafe { resizer.resize(&src, &mut dst.try_view_mut().unwrap()) };
+                    let src = src.try_crop(crop_box).unwrap();
+                    unsafe { resizer.resize(&src, &mut dst_cropped.try_view_mut().unwrap()) };
+                }
+                _ => unreachable!(),
+            }
+
+            let src_image =
+                DynamicImage::try_from_vec_u8(src_width, src_height, buffer.clone(), pixel_type)
+                    .unwrap();
+            assert_eq!(src_image.pixel_type(), pixel_type);
+            let src_view = src_image.try_view().unwrap();
+            assert_eq!(src_view.pixel_type(), pixel_type);
+            let mut dst_image = DynamicImage::new(dst_width, dst_height, pixel_type);
+            let mut dst_view = dst_image.try_view_mut().unwrap();
+            assert_eq!(dst_view.pixel_type(), pixel_type);
+            unsafe { resizer.resize_dynamic(&src_view, &mut dst_view) }.unwrap();
+            assert_eq!(dst_image.buffer(), expected.buffer());
+
+            let src_view = DynamicImageView::try_new(src_width, src_height, buffer, pixel_type)
+                .unwrap()
+                .try_crop(crop_box)
+                .unwrap();
+            assert_eq!(src_view.width().get(), 30);
+            assert_eq!(src_view.height().get(), 20);
+            let mut dst_image = DynamicImage::new(dst_width, dst_height, pixel_type);
+            unsafe { resizer.resize_dynamic(&src_view, &mut dst_image.try_view_mut().unwrap()) }
+                .unwrap();
+            assert_eq!(dst_image.buffer(), expected_cropped.buffer());
+        }
+
+        // Images with different types of pixels
+        let src_view =
+            DynamicImageView::try_new(src_width, src_height, &u8x3_buffer, PixelType::U8x3)
+                .unwrap();
+        let mut dst_image = DynamicImage::new(dst_width, dst_height, PixelType::F32);
+        let result =
+            unsafe { resizer.resize_dynamic(&src_view, &mut dst_image.try_view_mut().unwrap()) };
+        assert_eq!(result, Err(Error::DifferentTypesOfPixels));
+        assert!(dst_image.buffer().iter().all(|&v| v == 0));
+    }
+
+    #[test]
+    fn test_coefficients_cache() {
+        let src_width = NonZeroU32::new(40).unwrap();
+        let src_height = NonZeroU32::new(30).unwrap();
+        let buffer: Vec<u8> = (0..src_width.get() * src_height.get() * 3)
+            .map(|i| (i * 37 % 251) as u8)
+            .collect();
+        let src_image = Image::<U8x3>::try_from_vec_u8(src_width, src_height, buffer).unwrap();
+        let resize = |resizer: &mut Resizer, crop_box: CropBox, (width, height): (u32, u32)| {
+            let mut src_view = src_image.try_view().unwrap();
+            src_view.try_set_crop_box(crop_box).unwrap();
+            let mut dst_image = Image::<U8x3>::new(
+                NonZeroU32::new(width).unwrap(),
+                NonZeroU32::new(height).unwrap(),
+            );
+            unsafe { resizer.resize(&src_view, &mut dst_image.try_view_mut().unwrap()) };
+            dst_image.into_vec()
+        };
+
+        let full = CropBox::full(src_width, src_height);
+        let cropped = CropBox {
+            left: 3.5,
+            top: 2.,
+            width: 30.,
+            height: 20.25,
+        };
+        // Every change of geometry or filter has to invalidate cached
+        // coefficients, and repeated geometry has to use them.
+        let steps = [
+            (FilterType::Lanczos3, full, (13, 17)),
+            (FilterType::Lanczos3, full, (13, 17)),
+            (FilterType::Lanczos3, full, (17, 13)),
+            (FilterType::Lanczos3, cropped, (17, 13)),
+            (FilterType::Bilinear, cropped, (17, 13)),
+            (FilterType::Bilinear, cropped, (17, 13)),
+            (FilterType::Bilinear, full, (17, 13)),
+            (FilterType::Lanczos3, full, (13, 17)),
+        ];
+        let mut resizer = Resizer::new(ResizeAlg::Convolution(FilterType::Lanczos3));
+        for (filter_type, crop_box, dst_size) in steps {
+            resizer.algorithm = ResizeAlg::Convolution(filter_type.clone());
+            let mut fresh_resizer = Resizer::new(ResizeAlg::Convolution(filter_type));
+            assert_eq!(
+                resize(&mut resizer, crop_box, dst_size),
+                resize(&mut fresh_resizer, crop_box, dst_size)
+            );
+        }
+        for edge_mode in [EdgeMode::Clamp, EdgeMode::Wrap, EdgeMode::Truncate] {
+            resizer.set_edge_mode(edge_mode);
+            let mut fresh_resizer = Resizer::new(resizer.algorithm.clone());
+            fresh_resizer.set_edge_mode(edge_mode);
+            assert_eq!(
+                resize(&mut resizer, full, (13, 17)),
+                resize(&mut fresh_resizer, full, (13, 17))
+            );
+        }
+
+        // Cached coefficients are shared instead of being copied
+        let params =
+            resizer.convolution_params(FilterType::Lanczos3.into(), FilterType::Lanczos3.into());
+        let dst_width = NonZeroU32::new(13).unwrap();
+        let dst_height = NonZeroU32::new(17).unwrap();
+        let mut passes = || {
+            resizer.coefficients_cache.passes(
+                src_width,
+                src_height,
+                full,
+                dst_width,
+                dst_height,
+                params.clone(),
+            )
+        };
+        let (first, second) = (passes(), passes());
+        let (first, second) = (first.horiz_coeffs.unwrap(), second.horiz_coeffs.unwrap());
+        assert!(Arc::ptr_eq(&first.values, &second.values));
+        assert!(Arc::ptr_eq(&first.bounds, &second.bounds));
+    }
+
+    #[test]
+    fn test_blur() {
+        let src_width = NonZeroU32::new(41).unwrap();
+        let src_height = NonZeroU32::new(31).unwrap();
+        let buffer: Vec<u8> = (0..src_width.get() * src_height.get() * 4)
+            .map(|i| (i * 37 % 251) as u8)
+            .collect();
+        let src_image = Image::<U8x4>::try_from_vec_u8(src_width, src_height, buffer).unwrap();
+        let resize = |resizer: &mut Resizer, dst_width: u32, dst_height: u32| {
+            let dst_width = NonZeroU32::new(dst_width).unwrap();
+            let dst_height = NonZeroU32::new(dst_height).unwrap();
+            let mut dst_image = Image::<U8x4>::new(dst_width, dst_height);
+            unsafe {
+                resizer.resize(
+                    &src_image.try_view().unwrap(),
+                    &mut dst_image.try_view_mut().unwrap(),
+                )
+            };
+            dst_image.into_vec()
+        };
+        let variance = |buffer: &[u8]| {
+            let mean = buffer.iter().map(|&v| v as f64).sum::<f64>() / buffer.len() as f64;
+            buffer
+                .iter()
+                .map(|&v| (v as f64 - mean).powi(2))
+                .sum::<f64>()
+                / buffer.len() as f64
+        };
+
+        let mut resizer = Resizer::new(ResizeAlg::Convolution(FilterType::Lanczos3));
+        assert_eq!(resizer.try_set_blur(0.), Err(Error::InvalidBlurFactor));
+        assert_eq!(resizer.blur(), 1.);
+        let sharp = resize(&mut resizer, 13, 17);
+        resizer.try_set_blur(2.).unwrap();
+        let blurred = resize(&mut resizer, 13, 17);
+        assert!(variance(&blurred) < variance(&sharp));
+
+        // Box filter with very small support picks the nearest pixels.
+        // Odd sizes of source image guarantee that centers of destination
+        // pixels are never on the boundaries of source pixels.
+        let mut nearest_resizer = Resizer::new(ResizeAlg::Nearest);
+        let mut box_resizer = Resizer::new(ResizeAlg::Convolution(FilterType::Box));
+        box_resizer.try_set_blur(0.01).unwrap();
+        for (dst_width, dst_height) in [(13, 17), (70, 50), (40, 11)] {
+            assert_eq!(
+                resize(&mut box_resizer, dst_width, dst_height),
+                resize(&mut nearest_resizer, dst_width, dst_height)
+            );
+        }
+    }
+
+    #[test]
+    fn test_fractional_crop_box() {
+        // Every pixel of source image is equal to its column index
+        let src_width = NonZeroU32::new(32).unwrap();
+        let src_height = NonZeroU32::new(5).unwrap();
+        let src_pixels: Vec<F32> = (0..src_width.get() * src_height.get())
+            .map(|i| F32::new((i % src_width.get()) as f32))
+            .collect();
+        let mut src_view = ImageView::try_from_pixels(src_width, src_height, &src_pixels).unwrap();
+
+        // Shifting by a quarter of pixel without scaling
+        // interpolates neighboring columns.
+        src_view
+            .try_set_crop_box(CropBox {
+                left: 0.25,
+                top: 0.,
+                width: 30.,
+                height: 5.,
+            })
+            .unwrap();
+        let dst_width = NonZeroU32::new(30).unwrap();
+        let mut dst_image = Image::<F32>::new(dst_width, src_height);
+        let mut resizer = Resizer::new(ResizeAlg::Convolution(FilterType::Bilinear));
+        unsafe { resizer.resize(&src_view, &mut dst_image.try_view_mut().unwrap()) };
+        let dst_view = dst_image.try_view().unwrap();
+        for row in dst_view.iter_rows(0) {
+            for (x, pixel) in row.iter().enumerate() {
+                assert!(
+                    (pixel.0 - (x as f32 + 0.25)).abs() < 1e-5,
+                    "{} {}",
+                    x,
+                    pixel.0
+                );
+            }
+        }
+
+        // Crop box fitted into destination size is not rounded
+        let dst_width = NonZeroU32::new(3).unwrap();
+        let dst_height = NonZeroU32::new(2).unwrap();
+        src_view.set_crop_box_to_fit_dst_size(dst_width, dst_height, None);
+        let crop_box = src_view.crop_box();
+        assert_eq!(crop_box.width, 7.5);
+        assert_eq!(crop_box.left, 12.25);
+        assert_eq!((crop_box.top, crop_box.height), (0., 5.));
+    }
+
+    #[test]
+    fn test_pixel_alignment() {
+        let resize_row = |src: &[f32], dst_width: u32, alg: ResizeAlg, alignment| {
+            let src_pixels: Vec<F32> = src.iter().map(|&v| F32::new(v)).collect();
+            let src_width = NonZeroU32::new(src.len() as u32).unwrap();
+            let src_view =
+                ImageView::try_from_pixels(src_width, NonZeroU32::MIN, &src_pixels).unwrap();
+            let dst_width = NonZeroU32::new(dst_width).unwrap();
+            let mut dst_image = Image::<F32>::new(dst_width, NonZeroU32::MIN);
+            let mut resizer = Resizer::new(alg);
+            resizer.set_pixel_alignment(alignment);
+            unsafe { resizer.resize(&src_view, &mut dst_image.try_view_mut().unwrap()) };
+            let dst_view = dst_image.try_view().unwrap();
+            let values: Vec<f32> = dst_view.iter_rows(0).flatten().map(|p| p.0).collect();
+            values
+        };
+        let bilinear = ResizeAlg::Convolution(FilterType::Bilinear);
+        let nearest_axis = ResizeAlg::Separable {
+            horizontal: AxisAlg::Nearest,
+            vertical: FilterType::Bilinear.into(),
+        };
+
+        // Reference values are calculated with `torch.nn.functional.interpolate()`
+        // and `tf.compat.v1.image.resize()`.
+        let src = [0., 1., 2., 3.];
+        let cases = [
+            (
+                PixelAlignment::HalfPixel,
+                8,
+                vec![0., 0.25, 0.75, 1.25, 1.75, 2.25, 2.75, 3.],
+            ),
+            (
+                PixelAlignment::AlignCorners,
+                7,
+                vec![0., 0.5, 1., 1.5, 2., 2.5, 3.],
+            ),
+            (
+                PixelAlignment::Asymmetric,
+                8,
+                vec![0., 0.5, 1., 1.5, 2., 2.5, 3., 3.],
+            ),
+        ];
+        for (alignment, dst_width, expected) in cases {
+            let result = resize_row(&src, dst_width, bilinear.clone(), alignment);
+            for (value, expected_value) in result.iter().zip(&expected) {
+                assert!(
+                    (value - expected_value).abs() < 1e-6,
+                    "{:?} {:?}",
+                    alignment,
+                    result
+                );
+            }
+        }
+
+        let cases = [
+            (PixelAlignment::HalfPixel, 3, vec![1., 4., 6.]),
+            (PixelAlignment::AlignCorners, 3, vec![0., 4., 7.]),
+            (PixelAlignment::Asymmetric, 3, vec![0., 2., 5.]),
+            (
+                PixelAlignment::AlignCorners,
+                15,
+                vec![0., 1., 1., 2., 2., 3., 3., 4., 4., 5., 5., 6., 6., 7., 7.],
+            ),
+            (
+                PixelAlignment::Asymmetric,
+                16,
+                vec![
+                    0., 0., 1., 1., 2., 2., 3., 3., 4., 4., 5., 5., 6., 6., 7., 7.,
+                ],
+            ),
+        ];
+        let src = [0., 1., 2., 3., 4., 5., 6., 7.];
+        for (alignment, dst_width, expected) in cases {
+            for alg in [ResizeAlg::Nearest, nearest_axis.clone()] {
+                assert_eq!(resize_row(&src, dst_width, alg, alignment), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_edge_modes() {
+        let src_width = 13;
+        let src_height = 9;
+        let src_value = |x: u32, y: u32| ((x * 7 + y * 11) % 17) as f32;
+        // Source image extended by `PAD` pixels on each side
+        // according to the edge mode is used as reference.
+        const PAD: u32 = 16;
+        let padded_index = |edge_mode: EdgeMode, x: u32, size: u32| -> u32 {
+            let x = x as i32 - PAD as i32;
+            let size = size as i32;
+            let x = match edge_mode {
+                EdgeMode::Clamp => x.clamp(0, size - 1),
+                // Pixels are mirrored periodically if the padding
+                // is larger than the image.
+                EdgeMode::Mirror => match x.rem_euclid(2 * size) {
+                    x if x < size => x,
+                    x => 2 * size - x - 1,
+                },
+                EdgeMode::Wrap => x.rem_euclid(size),
+                _ => x,
+            };
+            x as u32
+        };
+        let resize = |edge_mode: EdgeMode,
+                      padded: bool,
+                      alg: &ResizeAlg,
+                      dst_width: u32,
+                      dst_height: u32|
+         -> Vec<f32> {
+            let pad = if padded { PAD } else { 0 };
+            let width = src_width + 2 * pad;
+            let height = src_height + 2 * pad;
+            let mut src_pixels = Vec::new();
+            for y in 0..height {
+                for x in 0..width {
+                    let value = match padded {
+                        true => src_value(
+                            padded_index(edge_mode, x, src_width),
+                            padded_index(edge_mode, y, src_height),
+                        ),
+                        false => src_value(x, y),
+                    };
+                    src_pixels.push(F32::new(value));
+                }
+            }
+            let mut src_view = ImageView::try_from_pixels(
+                NonZeroU32::new(width).unwrap(),
+                NonZeroU32::new(height).unwrap(),
+                &src_pixels,
+            )
+            .unwrap();
+            src_view
+                .try_set_crop_box(CropBox {
+                    left: pad as f64,
+                    top: pad as f64,
+                    width: src_width as f64,
+                    height: src_height as f64,
+                })
+                .unwrap();
+            let dst_width = NonZeroU32::new(dst_width).unwrap();
+            let dst_height = NonZeroU32::new(dst_height).unwrap();
+            let mut dst_image = Image::<F32>::new(dst_width, dst_height);
+            let mut resizer = Resizer::new(alg.clone());
+            if !padded {
+                resizer.set_edge_mode(edge_mode);
+            }
+            unsafe { resizer.resize(&src_view, &mut dst_image.try_view_mut().unwrap()) };
+            let dst_view = dst_image.try_view().unwrap();
+            let values: Vec<f32> = dst_view.iter_rows(0).flatten().map(|p| p.0).collect();
+            values
+        };
+
+        let algorithms = [
+            ResizeAlg::Convolution(FilterType::Lanczos3),
+            ResizeAlg::Convolution(FilterType::Mitchell),
+            ResizeAlg::Separable {
+                horizontal: AxisAlg::Nearest,
+                vertical: FilterType::CatmullRom.into(),
+            },
+            ResizeAlg::SuperSampling(FilterType::Bilinear, 2),
+        ];
+        for edge_mode in [EdgeMode::Clamp, EdgeMode::Mirror, EdgeMode::Wrap] {
+            for alg in algorithms.iter() {
+                for (dst_width, dst_height) in [(5, 4), (29, 20), (13, 4), (3, 3)] {
+                    // Wrapped padding of 3x3 image is wider than source image.
+                    // Super-sampling of it has no pixels out of temporary image,
+                    // so reference image can't be used.
+                    if dst_width == 3 && matches!(alg, ResizeAlg::SuperSampling(..)) {
+                        continue;
+                    }
+                    let result = resize(edge_mode, false, alg, dst_width, dst_height);
+                    let expected = resize(edge_mode, true, alg, dst_width, dst_height);
+                    for (value, expected_value) in result.iter().zip(&expected) {
+                        assert!(
+                            (value - expected_value).abs() < 1e-4,
+                            "{:?} {:?} {}x{}",
+                            edge_mode,
+                            alg,
+                            dst_width,
+                            dst_height
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_resize_to_fit() {
+        let src_width = NonZeroU32::new(40).unwrap();
+        let src_height = NonZeroU32::new(20).unwrap();
+        let buffer: Vec<u8> = (0..src_width.get() * src_height.get() * 4)
+            .map(|i| (i * 37 % 251) as u8)
+            .collect();
+        let src_image = Image::<U8x4>::try_from_vec_u8(src_width, src_height, buffer).unwrap();
+        let src_view = src_image.try_view().unwrap();
+        let background = U8x4::new(0x01020304);
+        let mut resizer = Resizer::default();
+
+        // Source image is resized to 30x15 and placed at the center
+        let dst_size = NonZeroU32::new(30).unwrap();
+        let mut dst_image = Image::<U8x4>::new(dst_size, dst_size);
+        unsafe {
+            resizer.resize_to_fit(
+                &src_view,
+                &mut dst_image.try_view_mut().unwrap(),
+                Some((0.5, 0.5)),
+                background,
+            )
+        };
+        let mut expected = Image::<U8x4>::new(dst_size, NonZeroU32::new(15).unwrap());
+        unsafe { resizer.resize(&src_view, &mut expected.try_view_mut().unwrap()) };
+        let dst_view = dst_image.try_view().unwrap();
+        for (y, row) in dst_view.iter_rows(0).enumerate() {
+            if (8..23).contains(&y) {
+                assert_eq!(
+                    row,
+                    expected.try_view().unwrap().get_row(y as u32 - 8).unwrap()
+                );
+            } else {
+                assert!(row.iter().all(|&p| p == background));
+            }
+        }
+
+        // Source image with the same height is copied to the right side
+        let mut dst_image = Image::<U8x4>::new(NonZeroU32::new(45).unwrap(), src_height);
+        resizer
+            .try_resize_to_fit(
+                &src_view,
+                &mut dst_image.try_view_mut().unwrap(),
+                Some((1., 0.)),
+                background,
+            )
+            .unwrap();
+        let dst_view = dst_image.try_view().unwrap();
+        for (row, src_row) in dst_view.iter_rows(0).zip(src_view.iter_rows(0)) {
+            assert!(row[..5].iter().all(|&p| p == background));
+            assert_eq!(&row[5..], src_row);
+        }
+    }
+
+    #[test]
+    fn test_resize_window() {
+        let src_width = NonZeroU32::new(53).unwrap();
+        let src_height = NonZeroU32::new(41).unwrap();
+        let buffer: Vec<u8> = (0..src_width.get() * src_height.get() * 3)
+            .map(|i| (i * 37 % 251) as u8)
+            .collect();
+        let src_image = Image::<U8x3>::try_from_vec_u8(src_width, src_height, buffer).unwrap();
+        let src_view = src_image.try_view().unwrap();
+        let virtual_width = NonZeroU32::new(31).unwrap();
+        let virtual_height = NonZeroU32::new(23).unwrap();
+
+        for (algorithm, edge_mode) in [
+            (ResizeAlg::Nearest, EdgeMode::Truncate),
+            (
+                ResizeAlg::Convolution(FilterType::Lanczos3),
+                EdgeMode::Truncate,
+            ),
+            (ResizeAlg::Convolution(FilterType::Bilinear), EdgeMode::Wrap),
+            (
+                ResizeAlg::SuperSampling(FilterType::Bilinear, 1),
+                EdgeMode::Truncate,
+            ),
+            (
+                ResizeAlg::Separable {
+                    horizontal: AxisAlg::Nearest,
+                    vertical: FilterType::CatmullRom.into(),
+                },
+                EdgeMode::Truncate,
+            ),
+        ] {
+            let mut resizer = Resizer::new(algorithm.clone());
+            resizer.set_edge_mode(edge_mode);
+            resizer.set_threads(NonZeroUsize::new(3).unwrap());
+            let mut expected = Image::<U8x3>::new(virtual_width, virtual_height);
+            unsafe { resizer.resize(&src_view, &mut expected.try_view_mut().unwrap()) };
+
+            // Virtual image is split into tiles with different sizes
+            for (left, width) in [(0, 12), (12, 1), (13, 18)] {
+                for (top, height) in [(0, 5), (5, 17), (22, 1)] {
+                    let width = NonZeroU32::new(width).unwrap();
+                    let height = NonZeroU32::new(height).unwrap();
+                    let mut tile = Image::<U8x3>::new(width, height);
+                    let window = DstWindow {
+                        width: virtual_width,
+                        height: virtual_height,
+                        left,
+                        top,
+                    };
+                    resizer
+                        .try_resize_window(&src_view, &mut tile.try_view_mut().unwrap(), window)
+                        .unwrap();
+
+                    let expected_view = expected.try_view().unwrap();
+                    let crop_box = CropBox {
+                        left: left as f64,
+                        top: top as f64,
+                        width: width.get() as f64,
+                        height: height.get() as f64,
+                    };
+                    let expected_tile = expected_view.try_sub_view(crop_box).unwrap();
+                    for (row, expected_row) in tile
+                        .try_view()
+                        .unwrap()
+                        .iter_rows(0)
+                        .zip(expected_tile.iter_rows(0))
+                    {
+                        assert_eq!(row, expected_row, "{:?} {:?}", algorithm, window);
+                    }
+                }
+            }
+        }
+
+        let mut tile = Image::<U8x3>::new(NonZeroU32::new(5).unwrap(), NonZeroU32::new(5).unwrap());
+        let window = DstWindow {
+            width: virtual_width,
+            height: virtual_height,
+            left: 27,
+            top: 0,
+        };
+        assert_eq!(
+            Resizer::default().try_resize_window(
+                &src_view,
+                &mut tile.try_view_mut().unwrap(),
+                window
+            ),
+            Err(Error::DstWindowOutOfBounds)
+        );
+    }
+
+    #[test]
+    fn test_sub_view() {
+        let src_width = NonZeroU32::new(40).unwrap();
+        let src_height = NonZeroU32::new(30).unwrap();
+        let buffer: Vec<u8> = (0..src_width.get() * src_height.get() * 4)
+            .map(|i| (i * 37 % 251) as u8)
+            .collect();
+        let src_image = Image::<U8x4>::try_from_vec_u8(src_width, src_height, buffer).unwrap();
+        let crop_box = CropBox {
+            left: 5.,
+            top: 3.,
+            width: 20.,
+            height: 17.,
+        };
+        let sub_width = NonZeroU32::new(20).unwrap();
+        let sub_height = NonZeroU32::new(17).unwrap();
+        let src_view = src_image.try_view().unwrap();
+        let sub_view = src_view.try_sub_view(crop_box).unwrap();
+        assert_eq!(sub_view.crop_box(), CropBox::full(sub_width, sub_height));
+
+        // Resizing of sub-view is the same as resizing of a copy of
+        // its pixels. Unlike crop box, pixels around the region are not used.
+        let sub_buffer: Vec<u8> = sub_view
+            .iter_rows(0)
+            .flat_map(|row| row.iter().flat_map(|p| p.0.to_le_bytes()))
+            .collect();
+        let sub_image = Image::<U8x4>::try_from_vec_u8(sub_width, sub_height, sub_buffer).unwrap();
+        let dst_width = NonZeroU32::new(11).unwrap();
+        let dst_height = NonZeroU32::new(7).unwrap();
+        let mut resizer = Resizer::default();
+        let mut expected = Image::<U8x4>::new(dst_width, dst_height);
+        unsafe {
+            resizer.resize(
+                &sub_image.try_view().unwrap(),
+                &mut expected.try_view_mut().unwrap(),
+            )
+        };
+        let mut result = Image::<U8x4>::new(dst_width, dst_height);
+        unsafe { resizer.resize(&sub_view, &mut result.try_view_mut().unwrap()) };
+        assert!(result.buffer() == expected.buffer());
+
+        // Other operations process only pixels of sub-view
+        let mul_div = MulDiv::default();
+        let mut premultiplied = Image::<U8x4>::new(src_width, src_height);
+        mul_div
+            .try_multiply_alpha(&src_view, &mut premultiplied.try_view_mut().unwrap())
+            .unwrap();
+        let mut sub_premultiplied = Image::<U8x4>::new(sub_width, sub_height);
+        mul_div
+            .try_multiply_alpha(&sub_view, &mut sub_premultiplied.try_view_mut().unwrap())
+            .unwrap();
+        let premultiplied_view = premultiplied.try_view().unwrap();
+        let expected_rows = premultiplied_view.try_crop(crop_box).unwrap();
+        for (row, expected_row) in sub_premultiplied
+            .try_view()
+            .unwrap()
+            .iter_rows(0)
+            .zip(expected_rows.iter_rows(0))
+        {
+            assert_eq!(row, expected_row);
+        }
+        assert_eq!(
+            src_view
+                .try_sub_view(CropBox {
+                    left: 0.5,
+                    ..crop_box
+                })
+                .err(),
+            Some(Error::NonIntegerCropBox)
+        );
+    }
+
+    #[test]
+    fn test_strided_images() {
+        let src_width = NonZeroU32::new(41).unwrap();
+        let src_height = NonZeroU32::new(31).unwrap();
+        let dst_width = NonZeroU32::new(13).unwrap();
+        let dst_height = NonZeroU32::new(17).unwrap();
+        let buffer: Vec<u8> = (0..src_width.get() * src_height.get() * 3)
+            .map(|i| (i * 37 % 251) as u8)
+            .collect();
+        let src_image = Image::<U8x3>::try_from_vec_u8(src_width, src_height, buffer).unwrap();
+        let mut dst_image = Image::<U8x3>::new(dst_width, dst_height);
+        let mut resizer = Resizer::new(ResizeAlg::Convolution(FilterType::Lanczos3));
+        unsafe {
+            resizer.resize(
+                &src_image.try_view().unwrap(),
+                &mut dst_image.try_view_mut().unwrap(),
+            )
+        };
+
+        // Rows of strided images are padded by 5 bytes
+        let src_stride = src_width.get() as usize * 3 + 5;
+        let mut src_buffer = vec![0xff; src_stride * src_height.get() as usize];
+        let src_rows = src_image
+            .buffer()
+            .chunks_exact(src_width.get() as usize * 3);
+        for (row, src_row) in src_buffer.chunks_mut(src_stride).zip(src_rows) {
+            row[..src_row.len()].copy_from_slice(src_row);
+        }
+        let src_strided = Image::<U8x3>::try_from_vec_u8_with_stride(
+            src_width, src_height, src_stride, src_buffer,
+        )
+        .unwrap();
+        let dst_stride = dst_width.get() as usize * 3 + 5;
+        let mut dst_buffer = vec![0xff; dst_stride * dst_height.get() as usize];
+        let mut dst_strided = Image::<U8x3>::try_from_slice_u8_with_stride(
+            dst_width,
+            dst_height,
+            dst_stride,
+            &mut dst_buffer,
+        )
+        .unwrap();
+        unsafe {
+            resizer.resize(
+                &src_strided.try_view().unwrap(),
+                &mut dst_strided.try_view_mut().unwrap(),
+            )
+        };
+
+        let row_size = dst_width.get() as usize * 3;
+        let dst_rows = dst_image.buffer().chunks_exact(row_size);
+        for (row, dst_row) in dst_buffer.chunks(dst_stride).zip(dst_rows) {
+            assert_eq!(&row[..row_size], dst_row);
+            // Padding of rows is not changed
+            assert!(row[row_size..].iter().all(|&v| v == 0xff));
+        }
+    }
+
+    #[test]
+    fn test_stride_not_multiple_of_pixel_size() {
+        let width = NonZeroU32::new(3).unwrap();
+        let height = NonZeroU32::new(2).unwrap();
+        // The last row starts at 10 bytes and ends exactly at the end
+        // of buffer, which is not a whole number of pixels.
+        let buffer: Vec<u8> = (0..19).collect();
+        let src_view = ImageView::<U8x3>::try_with_stride(width, height, 10, &buffer).unwrap();
+        let last_row = src_view.get_row(1).unwrap();
+        assert_eq!(U8x3::components(last_row), &buffer[10..]);
+
+        let dst_height = NonZeroU32::new(4).unwrap();
+        let mut dst_image = Image::<U8x3>::new(width, dst_height);
+        let mut resizer = Resizer::new(ResizeAlg::Nearest);
+        unsafe { resizer.resize(&src_view, &mut dst_image.try_view_mut().unwrap()) };
+        let dst_rows: Vec<&[u8]> = dst_image.buffer().chunks_exact(9).collect();
+        assert_eq!(dst_rows[3], &buffer[10..]);
+    }
+
+    #[test]
+    fn test_separable() {
+        let src_width = NonZeroU32::new(41).unwrap();
+        let src_height = NonZeroU32::new(31).unwrap();
+        let buffer: Vec<u8> = (0..src_width.get() * src_height.get() * 4)
+            .map(|i| (i * 37 % 251) as u8)
+            .collect();
+        let src_image = Image::<U8x4>::try_from_vec_u8(src_width, src_height, buffer).unwrap();
+        let resize = |src_image: &Image<U8x4>, alg: ResizeAlg, dst_width: u32, dst_height: u32| {
+            let dst_width = NonZeroU32::new(dst_width).unwrap();
+            let dst_height = NonZeroU32::new(dst_height).unwrap();
+            let mut dst_image = Image::<U8x4>::new(dst_width, dst_height);
+            let mut resizer = Resizer::new(alg);
+            unsafe {
+                resizer.resize(
+                    &src_image.try_view().unwrap(),
+                    &mut dst_image.try_view_mut().unwrap(),
+                )
+            };
+            dst_image
+        };
+
+        for (dst_width, dst_height) in [(13, 17), (70, 50), (40, 11)] {
+            let separable = ResizeAlg::Separable {
+                horizontal: AxisAlg::Nearest,
+                vertical: AxisAlg::Nearest,
+            };
+            assert_eq!(
+                resize(&src_image, separable, dst_width, dst_height).into_vec(),
+                resize(&src_image, ResizeAlg::Nearest, dst_width, dst_height).into_vec()
+            );
+
+            let separable = ResizeAlg::Separable {
+                horizontal: FilterType::Lanczos3.into(),
+                vertical: FilterType::Lanczos3.into(),
+            };
+            let convolution = ResizeAlg::Convolution(FilterType::Lanczos3);
+            assert_eq!(
+                resize(&src_image, separable, dst_width, dst_height).into_vec(),
+                resize(&src_image, convolution, dst_width, dst_height).into_vec()
+            );
+
+            // Mixed algorithms give the same result as resizing
+            // along each axis separately.
+            let separable = ResizeAlg::Separable {
+                horizontal: AxisAlg::Nearest,
+                vertical: FilterType::Mitchell.into(),
+            };
+            let tmp_image = resize(&src_image, ResizeAlg::Nearest, dst_width, src_height.get());
+            let convolution = ResizeAlg::Convolution(FilterType::Mitchell);
+            assert_eq!(
+                resize(&src_image, separable, dst_width, dst_height).into_vec(),
+                resize(&tmp_image, convolution, dst_width, dst_height).into_vec()
+            );
+
+            let separable = ResizeAlg::Separable {
+                horizontal: FilterType::CatmullRom.into(),
+                vertical: AxisAlg::Nearest,
+            };
+            let tmp_image = resize(&src_image, ResizeAlg::Nearest, src_width.get(), dst_height);
+            let convolution = ResizeAlg::Convolution(FilterType::CatmullRom);
+            assert_eq!(
+                resize(&src_image, separable, dst_width, dst_height).into_vec(),
+                resize(&tmp_image, convolution, dst_width, dst_height).into_vec()
+            );
+        }
     }
 }
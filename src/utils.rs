@@ -16,3 +16,25 @@ pub(crate) fn foreach_with_pre_reading<D, I>(
         process_data(next_data);
     }
 }
+
+/// Evaluates the same expression for any variant of dynamic image
+/// (or view) with the wrapped generic value bound to the given name.
+macro_rules! dynamic_map {
+    ($enum:ident, $value:expr, |$inner:ident| $body:expr) => {
+        match $value {
+            $enum::U8($inner) => $body,
+            $enum::U8x2($inner) => $body,
+            $enum::U8x3($inner) => $body,
+            $enum::U8x4($inner) => $body,
+            $enum::U16($inner) => $body,
+            $enum::U16x2($inner) => $body,
+            $enum::U16x3($inner) => $body,
+            $enum::U16x4($inner) => $body,
+            $enum::I32($inner) => $body,
+            $enum::F32($inner) => $body,
+            $enum::F32x2($inner) => $body,
+            $enum::F32x3($inner) => $body,
+            $enum::F32x4($inner) => $body,
+        }
+    };
+}